@@ -107,6 +107,34 @@ fn generate_sim(first: bool) -> Simulator {
     sim
 }
 
+fn generate_adder_chain(ordered_start: bool) -> Simulator {
+    const WIDTH_32: BitWidth = bit_width!(32);
+
+    let mut builder = SimulatorBuilder::default();
+
+    let one = builder.add_wire(WIDTH_32).unwrap();
+    builder
+        .set_wire_drive(one, &LogicState::from_u32(1, WIDTH_32))
+        .unwrap();
+
+    let mut sum = builder.add_wire(WIDTH_32).unwrap();
+    builder
+        .set_wire_drive(sum, &LogicState::from_u32(0, WIDTH_32))
+        .unwrap();
+
+    for _ in 0..10000 {
+        let next = builder.add_wire(WIDTH_32).unwrap();
+        let _id = builder.add_add(sum, one, next).unwrap();
+        sum = next;
+    }
+
+    if ordered_start {
+        builder.build_with_ordered_start()
+    } else {
+        builder.build()
+    }
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     let mut first = true;
 
@@ -125,6 +153,23 @@ fn criterion_benchmark(c: &mut Criterion) {
             BatchSize::LargeInput,
         )
     });
+
+    for (name, ordered_start) in [
+        ("adder_chain_first_eval", false),
+        ("adder_chain_first_eval_ordered", true),
+    ] {
+        c.bench_function(name, |b| {
+            b.iter_batched(
+                || generate_adder_chain(ordered_start),
+                |mut sim| {
+                    sim.reset();
+                    let result = sim.run_sim(u64::MAX);
+                    assert!(matches!(result, SimulationRunResult::Ok));
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -166,26 +166,17 @@ fn drive(args: ArgMatches, context: &mut Context) -> Result<Option<String>> {
         return Ok(None);
     };
 
-    let new_state = if new_state.starts_with('d') {
-        u32::from_str_radix(&new_state[1..], 10)
-            .ok()
-            .map(LogicState::from_int)
-    } else if new_state.starts_with('h') {
-        u32::from_str_radix(&new_state[1..], 16)
-            .ok()
-            .map(LogicState::from_int)
-    } else {
-        LogicState::parse(new_state).ok()
-    };
-
-    let Some(new_state) = new_state else {
-        println!("Error parsing new state");
-        return Ok(None);
+    let input_width = context.sim.get_wire_width(input_wire).unwrap();
+    let new_state = match LogicState::parse_prefixed(new_state, input_width) {
+        Ok(new_state) => new_state,
+        Err(err) => {
+            println!("Error parsing new state: {err}");
+            return Ok(None);
+        }
     };
 
     context.sim.set_wire_drive(input_wire, &new_state).unwrap();
 
-    let input_width = context.sim.get_wire_width(input_wire).unwrap();
     let result = format!(
         "Driving input `{input_name}' to '{}'",
         new_state.display_string(input_width)
@@ -9,6 +9,9 @@ use itertools::izip;
 use smallvec::smallvec;
 #[cfg(feature = "dot-export")]
 use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
 use sync_unsafe_cell::SyncUnsafeCell;
 
 def_id_type!(
@@ -16,6 +19,9 @@ def_id_type!(
     pub ComponentId
 );
 
+/// The maximum number of components of a single kind, limited by the 24 bit index of a [ComponentId]
+pub(crate) const MAX_COMPONENTS_PER_KIND: usize = 1 << 24;
+
 impl ComponentId {
     #[inline]
     const fn kind(self) -> u8 {
@@ -56,6 +62,9 @@ pub(crate) trait Component: Sized {
 
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth);
 
+    /// Adds a copy of this component to another circuit, translating all wires using `wire_map`
+    fn copy_into(&self, builder: &mut SimulatorBuilder, wire_map: &WireMap) -> AddComponentResult;
+
     fn update(
         &mut self,
         wire_states: WireStateView,
@@ -64,27 +73,124 @@ pub(crate) trait Component: Sized {
 
     #[inline]
     fn reset(&mut self) {}
+
+    /// The contents of the memory held by this component, if it has any
+    #[inline]
+    fn memory(&self) -> Option<Vec<InlineLogicState>> {
+        None
+    }
+
+    /// Replaces the contents of the memory held by this component
+    ///
+    /// The contents must fit the layout returned by [Component::memory_layout].
+    #[inline]
+    fn restore_memory(&mut self, _memory: &[InlineLogicState]) {}
+
+    /// The clock input of a clocked component, all of its other inputs are only sampled on clock edges
+    #[inline]
+    fn clock(&self) -> Option<WireStateId> {
+        None
+    }
+
+    /// The width and the allowed numbers of entries of the memory held by this component, if it has any
+    #[inline]
+    fn memory_layout(&self) -> Option<(BitWidth, RangeInclusive<usize>)> {
+        None
+    }
+
+    /// The value stored by a register-like component, which it shows on its output
+    #[inline]
+    fn register_value(&self) -> Option<LogicStateRef<'_>> {
+        None
+    }
+
+    /// Replaces the value stored by a register-like component
+    ///
+    /// Only called on components that return a value from [Component::register_value].
+    #[inline]
+    fn set_register_value(&mut self, _value: LogicStateRef<'_>) {}
 }
 
 pub(crate) trait ComponentAuto: Component {
     const ID: u8;
+    const KIND: ComponentKind;
 
     fn extract_storage(storage: &ComponentStorage) -> &[SyncUnsafeCell<Self>];
     fn extract_storage_mut(storage: &mut ComponentStorage) -> &mut Vec<SyncUnsafeCell<Self>>;
+
+    /// Replaces all references to tied wires with references to their root
+    fn remap_wires(&mut self, remap: &WireRemap<'_>);
+}
+
+/// Maps tied wires and their original states to the wire they are tied to
+pub(crate) struct WireRemap<'a> {
+    pub(crate) wires: &'a HashMap<WireId, WireId>,
+    pub(crate) states: &'a HashMap<WireStateId, WireStateId>,
+}
+
+/// A field of a component that may refer to a wire
+trait RemapWires {
+    #[inline]
+    fn remap_wires(&mut self, _remap: &WireRemap<'_>) {}
+}
+
+impl RemapWires for WireId {
+    #[inline]
+    fn remap_wires(&mut self, remap: &WireRemap<'_>) {
+        if let Some(&root) = remap.wires.get(self) {
+            *self = root;
+        }
+    }
+}
+
+impl RemapWires for WireStateId {
+    #[inline]
+    fn remap_wires(&mut self, remap: &WireRemap<'_>) {
+        if let Some(&root) = remap.states.get(self) {
+            *self = root;
+        }
+    }
+}
+
+impl RemapWires for IdVec<WireStateId> {
+    #[inline]
+    fn remap_wires(&mut self, remap: &WireRemap<'_>) {
+        for state in self.as_mut_slice() {
+            state.remap_wires(remap);
+        }
+    }
 }
 
+impl RemapWires for OutputStateId {}
+impl RemapWires for BitWidth {}
+impl RemapWires for bool {}
+impl RemapWires for u8 {}
+impl RemapWires for Option<bool> {}
+impl RemapWires for NonZeroUsize {}
+impl RemapWires for EdgeKind {}
+impl RemapWires for RotateDirection {}
+impl RemapWires for LogicBitState {}
+impl RemapWires for ClockTrigger {}
+impl RemapWires for EnablePolarity {}
+impl RemapWires for InlineLogicState {}
+impl RemapWires for VecDeque<InlineLogicState> {}
+impl RemapWires for Box<[InlineLogicState]> {}
+impl RemapWires for Arc<[InlineLogicState]> {}
+
 macro_rules! def_components {
     (@SINGLE $id:expr;
         struct $component_name:ident {
             $($field_name:ident : $field_ty:ty,)*
         }
     ) => {
+        #[derive(Clone)]
         pub(crate) struct $component_name {
             $($field_name : $field_ty,)*
         }
 
         impl ComponentAuto for $component_name {
             const ID: u8 = $id;
+            const KIND: ComponentKind = ComponentKind::$component_name;
 
             #[inline]
             fn extract_storage(storage: &ComponentStorage) -> &[SyncUnsafeCell<Self>] {
@@ -95,6 +201,11 @@ macro_rules! def_components {
             fn extract_storage_mut(storage: &mut ComponentStorage) -> &mut Vec<SyncUnsafeCell<Self>> {
                 &mut storage.$component_name
             }
+
+            #[inline]
+            fn remap_wires(&mut self, remap: &WireRemap<'_>) {
+                $(RemapWires::remap_wires(&mut self.$field_name, remap);)*
+            }
         }
     };
 
@@ -159,20 +270,62 @@ macro_rules! def_components {
             )+
         }
 
-        #[derive(Default)]
+        /// The kinds of components a simulation can contain
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum ComponentKind {
+            $(
+                #[allow(missing_docs)]
+                $component_name,
+            )+
+        }
+
         pub(crate) struct ComponentStorage {
             $(
                 #[allow(non_snake_case)]
                 $component_name: Vec<SyncUnsafeCell<$component_name>>,
             )+
+            kind_capacity: usize,
+        }
+
+        impl Clone for ComponentStorage {
+            fn clone(&self) -> Self {
+                Self {
+                    $(
+                        $component_name: self.$component_name
+                            .iter()
+                            .map(|component| {
+                                let component = unsafe {
+                                    // SAFETY: since we have a shared reference to `self`, no mutable references exist
+                                    &*component.get()
+                                };
+                                SyncUnsafeCell::new(component.clone())
+                            })
+                            .collect(),
+                    )+
+                    kind_capacity: self.kind_capacity,
+                }
+            }
+        }
+
+        impl Default for ComponentStorage {
+            fn default() -> Self {
+                Self {
+                    $(
+                        $component_name: Vec::new(),
+                    )+
+                    kind_capacity: MAX_COMPONENTS_PER_KIND,
+                }
+            }
         }
 
         impl ComponentStorage {
             pub(crate) fn push<T: ComponentAuto>(&mut self, component: T) -> Option<ComponentId> {
+                let kind_capacity = self.kind_capacity;
                 let storage = T::extract_storage_mut(self);
 
                 let index = storage.len();
-                if index > 0xFFFFFF {
+                if index >= kind_capacity {
                     return None;
                 }
 
@@ -193,6 +346,45 @@ macro_rules! def_components {
                 iter
             }
 
+            pub(crate) fn component_count(&self) -> usize {
+                0 $(+ self.$component_name.len())+
+            }
+
+            pub(crate) fn kind_count(&self, kind: ComponentKind) -> usize {
+                match kind {
+                    $(
+                        ComponentKind::$component_name => self.$component_name.len(),
+                    )+
+                }
+            }
+
+            pub(crate) fn remap_wires(&mut self, remap: &WireRemap<'_>) {
+                $(
+                    for component in &mut self.$component_name {
+                        component.get_mut().remap_wires(remap);
+                    }
+                )+
+            }
+
+            pub(crate) fn reserve(&mut self, kind: ComponentKind, additional: usize) {
+                match kind {
+                    $(
+                        ComponentKind::$component_name => self.$component_name.reserve(additional),
+                    )+
+                }
+            }
+
+            #[inline]
+            pub(crate) fn remaining_capacity(&self, kind: ComponentKind) -> usize {
+                self.kind_capacity.saturating_sub(self.kind_count(kind))
+            }
+
+            /// Lowers the number of components allowed per kind, so the limit can be reached in tests
+            #[cfg(test)]
+            pub(crate) fn set_kind_capacity(&mut self, kind_capacity: usize) {
+                self.kind_capacity = kind_capacity.min(MAX_COMPONENTS_PER_KIND);
+            }
+
             pub(crate) fn component_exists(&self, id: ComponentId) -> bool {
                 match id.kind() {
                     $(
@@ -205,6 +397,109 @@ macro_rules! def_components {
                 }
             }
 
+            pub(crate) fn kind(&self, id: ComponentId) -> ComponentKind {
+                match id.kind() {
+                    $(
+                        <$component_name>::ID => ComponentKind::$component_name,
+                    )+
+                    _ => panic!("invalid component kind"),
+                }
+            }
+
+            pub(crate) fn kind_name(&self, id: ComponentId) -> &'static str {
+                match id.kind() {
+                    $(
+                        <$component_name>::ID => stringify!($component_name),
+                    )+
+                    _ => panic!("invalid component kind"),
+                }
+            }
+
+            pub(crate) fn output_range(&self, id: ComponentId) -> (OutputStateId, OutputStateId, BitWidth) {
+                match id.kind() {
+                    $(
+                        <$component_name>::ID => {
+                            let storage = <$component_name>::extract_storage(self);
+                            let component = unsafe {
+                                // SAFETY: we have a shared reference to `self`, so no mutable references exist
+                                &*storage[id.index()].get()
+                            };
+
+                            component.output_range()
+                        }
+                    )+
+                    _ => panic!("invalid component kind"),
+                }
+            }
+
+            pub(crate) fn clock(&self, id: ComponentId) -> Option<WireStateId> {
+                match id.kind() {
+                    $(
+                        <$component_name>::ID => {
+                            let storage = <$component_name>::extract_storage(self);
+                            let component = unsafe {
+                                // SAFETY: we have a shared reference to `self`, so no mutable references exist
+                                &*storage[id.index()].get()
+                            };
+
+                            component.clock()
+                        }
+                    )+
+                    _ => panic!("invalid component kind"),
+                }
+            }
+
+            pub(crate) fn register_value(&self, id: ComponentId) -> Option<LogicStateRef<'_>> {
+                match id.kind() {
+                    $(
+                        <$component_name>::ID => {
+                            let storage = <$component_name>::extract_storage(self);
+                            let component = unsafe {
+                                // SAFETY: we have a shared reference to `self`, so no mutable references exist
+                                &*storage[id.index()].get()
+                            };
+
+                            component.register_value()
+                        }
+                    )+
+                    _ => panic!("invalid component kind"),
+                }
+            }
+
+            pub(crate) fn set_register_value(&mut self, id: ComponentId, value: LogicStateRef<'_>) {
+                match id.kind() {
+                    $(
+                        <$component_name>::ID => {
+                            let storage = <$component_name>::extract_storage_mut(self);
+                            storage[id.index()].get_mut().set_register_value(value);
+                        }
+                    )+
+                    _ => panic!("invalid component kind"),
+                }
+            }
+
+            pub(crate) fn copy_component_into(
+                &self,
+                id: ComponentId,
+                builder: &mut SimulatorBuilder,
+                wire_map: &WireMap,
+            ) -> AddComponentResult {
+                match id.kind() {
+                    $(
+                        <$component_name>::ID => {
+                            let storage = <$component_name>::extract_storage(self);
+                            let component = unsafe {
+                                // SAFETY: we have a shared reference to `self`, so no mutable references exist
+                                &*storage[id.index()].get()
+                            };
+
+                            component.copy_into(builder, wire_map)
+                        }
+                    )+
+                    _ => panic!("invalid component kind"),
+                }
+            }
+
             // TODO: instead of matching on kind, divide the update queue by kind and then loop over each kind.
             /// SAFETY: caller must ensure the component ID is valid and unique.
             pub(crate) unsafe fn update_component(
@@ -240,6 +535,70 @@ macro_rules! def_components {
                     }
                 )+
             }
+
+            pub(crate) fn checkpoint_memory(&self) -> HashMap<ComponentId, Vec<InlineLogicState>> {
+                let mut contents = HashMap::new();
+                $(
+                    let storage = <$component_name>::extract_storage(self);
+                    for (index, component) in storage.iter().enumerate() {
+                        let component = unsafe {
+                            // SAFETY: we have a shared reference to `self`, so no mutable references exist
+                            &*component.get()
+                        };
+
+                        if let Some(memory) = component.memory() {
+                            let id = ((<$component_name>::ID as u32) << 24) | (index as u32);
+                            contents.insert(ComponentId(id), memory);
+                        }
+                    }
+                )+
+                contents
+            }
+
+            pub(crate) fn memory_layouts(&self) -> Vec<(ComponentId, BitWidth, RangeInclusive<usize>)> {
+                let mut layouts = Vec::new();
+                $(
+                    let storage = <$component_name>::extract_storage(self);
+                    for (index, component) in storage.iter().enumerate() {
+                        let component = unsafe {
+                            // SAFETY: we have a shared reference to `self`, so no mutable references exist
+                            &*component.get()
+                        };
+
+                        if let Some((bit_width, lens)) = component.memory_layout() {
+                            let id = ((<$component_name>::ID as u32) << 24) | (index as u32);
+                            layouts.push((ComponentId(id), bit_width, lens));
+                        }
+                    }
+                )+
+                layouts
+            }
+
+            /// Checks whether `contents` holds the memory of exactly the memory components, each fitting its layout
+            pub(crate) fn memory_fits(&self, contents: &HashMap<ComponentId, Vec<InlineLogicState>>) -> bool {
+                let layouts = self.memory_layouts();
+                (layouts.len() == contents.len())
+                    && layouts.into_iter().all(|(id, bit_width, lens)| {
+                        contents.get(&id).is_some_and(|memory| {
+                            lens.contains(&memory.len())
+                                && memory.iter().all(|entry| entry.bit_width() == bit_width)
+                        })
+                    })
+            }
+
+            pub(crate) fn restore_memory(&mut self, contents: &HashMap<ComponentId, Vec<InlineLogicState>>) {
+                for (&id, memory) in contents {
+                    match id.kind() {
+                        $(
+                            <$component_name>::ID => {
+                                let storage = <$component_name>::extract_storage_mut(self);
+                                storage[id.index()].get_mut().restore_memory(memory);
+                            }
+                        )+
+                        _ => panic!("invalid component kind"),
+                    }
+                }
+            }
         }
     };
 }
@@ -346,15 +705,16 @@ def_components! {
         bit_width: BitWidth,
         input: WireStateId,
         enable: WireStateId,
+        disabled_value: LogicBitState,
         output_state: OutputStateId,
         output_wire: WireId,
     }
 
     struct Slice {
         bit_width: BitWidth,
+        input_width: BitWidth,
         input: WireStateId,
-        start_offset: u16,
-        end_offset: u16,
+        offset: u8,
         output_state: OutputStateId,
         output_wire: WireId,
     }
@@ -390,6 +750,30 @@ def_components! {
         output_wire: WireId,
     }
 
+    struct MulSplit {
+        bit_width: BitWidth,
+        signed: bool,
+        input_a: WireStateId,
+        input_b: WireStateId,
+        low_state: OutputStateId,
+        high_state: OutputStateId,
+        low_wire: WireId,
+        high_wire: WireId,
+    }
+
+    struct RotateThroughCarry {
+        bit_width: BitWidth,
+        amount_width: BitWidth,
+        direction: RotateDirection,
+        input: WireStateId,
+        amount: WireStateId,
+        carry_in: WireStateId,
+        output_state: OutputStateId,
+        carry_out_state: OutputStateId,
+        output_wire: WireId,
+        carry_out_wire: WireId,
+    }
+
     struct LeftShift {
         bit_width: BitWidth,
         input_a: WireStateId,
@@ -536,6 +920,19 @@ def_components! {
         output_wire: WireId,
     }
 
+    struct MagnitudeCompare {
+        bit_width: BitWidth,
+        signed: bool,
+        input_a: WireStateId,
+        input_b: WireStateId,
+        less_state: OutputStateId,
+        equal_state: OutputStateId,
+        greater_state: OutputStateId,
+        less_wire: WireId,
+        equal_wire: WireId,
+        greater_wire: WireId,
+    }
+
     struct ZeroExtend {
         bit_width: BitWidth,
         input: WireStateId,
@@ -549,6 +946,80 @@ def_components! {
         output_state: OutputStateId,
         output_wire: WireId,
     }
+
+    struct EdgeDetector {
+        input: WireStateId,
+        clock: WireStateId,
+        clock_trigger: ClockTrigger,
+        edge: EdgeKind,
+        prev_input: Option<bool>,
+        pulse: bool,
+        output_state: OutputStateId,
+        output_wire: WireId,
+    }
+
+    struct Fifo {
+        bit_width: BitWidth,
+        data_in: WireStateId,
+        push: WireStateId,
+        pop: WireStateId,
+        clock: WireStateId,
+        clock_trigger: ClockTrigger,
+        depth: NonZeroUsize,
+        queue: VecDeque<InlineLogicState>,
+        data_out_state: OutputStateId,
+        full_state: OutputStateId,
+        empty_state: OutputStateId,
+        data_out_wire: WireId,
+        full_wire: WireId,
+        empty_wire: WireId,
+    }
+
+    struct Delay {
+        bit_width: BitWidth,
+        input: WireStateId,
+        clock: WireStateId,
+        clock_trigger: ClockTrigger,
+        value: InlineLogicState,
+        output_state: OutputStateId,
+        output_wire: WireId,
+    }
+
+    struct Register {
+        bit_width: BitWidth,
+        data_in: WireStateId,
+        enable: WireStateId,
+        enable_polarity: EnablePolarity,
+        clock: WireStateId,
+        clock_trigger: ClockTrigger,
+        value: InlineLogicState,
+        output_state: OutputStateId,
+        output_wire: WireId,
+    }
+
+    struct Ram {
+        addr_width: BitWidth,
+        data_width: BitWidth,
+        write_addr: WireStateId,
+        data_in: WireStateId,
+        read_addr: WireStateId,
+        write: WireStateId,
+        write_polarity: EnablePolarity,
+        clock: WireStateId,
+        clock_trigger: ClockTrigger,
+        memory: Box<[InlineLogicState]>,
+        output_state: OutputStateId,
+        output_wire: WireId,
+    }
+
+    struct Rom {
+        addr_width: BitWidth,
+        data_width: BitWidth,
+        addr: WireStateId,
+        memory: Arc<[InlineLogicState]>,
+        output_state: OutputStateId,
+        output_wire: WireId,
+    }
 }
 
 impl ComponentArgs for () {
@@ -631,6 +1102,17 @@ macro_rules! unary_gate_impl {
             smallvec![(self.input, format!("In").into())]
         }
 
+        fn copy_into(
+            &self,
+            builder: &mut SimulatorBuilder,
+            wire_map: &WireMap,
+        ) -> AddComponentResult {
+            builder.add_component::<Self>(UnaryGateArgs {
+                input: wire_map.input(self.input),
+                output: wire_map.output(self.output_wire),
+            })
+        }
+
         #[inline]
         fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
             (self.output_state, self.output_state, self.bit_width)
@@ -722,6 +1204,18 @@ macro_rules! binary_gate_impl {
             ]
         }
 
+        fn copy_into(
+            &self,
+            builder: &mut SimulatorBuilder,
+            wire_map: &WireMap,
+        ) -> AddComponentResult {
+            builder.add_component::<Self>(BinaryGateArgs {
+                input_a: wire_map.input(self.input_a),
+                input_b: wire_map.input(self.input_b),
+                output: wire_map.output(self.output_wire),
+            })
+        }
+
         #[inline]
         fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
             (self.output_state, self.output_state, self.bit_width)
@@ -873,6 +1367,23 @@ macro_rules! wide_gate_impl {
                 .collect()
         }
 
+        fn copy_into(
+            &self,
+            builder: &mut SimulatorBuilder,
+            wire_map: &WireMap,
+        ) -> AddComponentResult {
+            let inputs: SmallVec<[_; 4]> = self
+                .inputs
+                .iter()
+                .map(|input| wire_map.input(input))
+                .collect();
+
+            builder.add_component::<Self>(WideGateArgs {
+                inputs: &inputs,
+                output: wire_map.output(self.output_wire),
+            })
+        }
+
         #[inline]
         fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
             (self.output_state, self.output_state, self.bit_width)
@@ -1040,22 +1551,44 @@ impl Component for NotGate {
     }
 }
 
-impl Component for Buffer {
-    type Args<'a> = BinaryGateArgs;
+#[derive(Clone, Copy)]
+pub(crate) struct BufferArgs {
+    pub(crate) input: WireId,
+    pub(crate) enable: WireId,
+    pub(crate) output: WireId,
+    pub(crate) disabled_value: LogicBitState,
+}
 
-    fn new(
-        args: Self::Args<'_>,
+impl ComponentArgs for BufferArgs {
+    fn connect_drivers(
+        self,
+        component: ComponentId,
         wires: &mut WireList,
-        output_states: &mut OutputStateAllocator,
-    ) -> Result<Self, AddComponentError> {
+    ) -> Result<(), AddComponentError> {
+        let input = wires.get_mut(self.input).ok_or(InvalidWireIdError)?;
+        input.add_driving(component);
+        let enable = wires.get_mut(self.enable).ok_or(InvalidWireIdError)?;
+        enable.add_driving(component);
+        Ok(())
+    }
+}
+
+impl Component for Buffer {
+    type Args<'a> = BufferArgs;
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
         let output_wire = wires
             .get(args.output)
             .ok_or(AddComponentError::InvalidWireId)?;
         let input_wire = wires
-            .get(args.input_a)
+            .get(args.input)
             .ok_or(AddComponentError::InvalidWireId)?;
         let enable_wire = wires
-            .get(args.input_b)
+            .get(args.enable)
             .ok_or(AddComponentError::InvalidWireId)?;
 
         if input_wire.bit_width() != output_wire.bit_width() {
@@ -1079,6 +1612,7 @@ impl Component for Buffer {
             bit_width: output_wire.bit_width(),
             input,
             enable,
+            disabled_value: args.disabled_value,
             output_state,
             output_wire: args.output,
         })
@@ -1102,6 +1636,15 @@ impl Component for Buffer {
         ]
     }
 
+    fn copy_into(&self, builder: &mut SimulatorBuilder, wire_map: &WireMap) -> AddComponentResult {
+        builder.add_component::<Self>(BufferArgs {
+            input: wire_map.input(self.input),
+            enable: wire_map.input(self.enable),
+            output: wire_map.output(self.output_wire),
+            disabled_value: self.disabled_value,
+        })
+    }
+
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
         (self.output_state, self.output_state, self.bit_width)
@@ -1122,7 +1665,12 @@ impl Component for Buffer {
             .expect("invalid wire state ID");
 
         match enable.bit(0).expect("invalid wire width") {
-            LogicBitState::Logic0 | LogicBitState::HighZ => tmp_state.set_high_z(),
+            LogicBitState::Logic0 | LogicBitState::HighZ => match self.disabled_value {
+                LogicBitState::HighZ => tmp_state.set_high_z(),
+                LogicBitState::Undefined => tmp_state.set_undefined(),
+                LogicBitState::Logic0 => tmp_state.set_logic_0(),
+                LogicBitState::Logic1 => tmp_state.set_logic_1(),
+            },
             LogicBitState::Logic1 => unary_op(tmp_state.borrow_mut(), input, high_z_to_undefined),
             LogicBitState::Undefined => tmp_state.set_undefined(),
         }
@@ -1138,30 +1686,90 @@ impl Component for Buffer {
     }
 }
 
+#[derive(Clone, Copy)]
+pub(crate) struct SliceArgs {
+    pub(crate) input: WireId,
+    pub(crate) offset: u8,
+    pub(crate) output: WireId,
+}
+
+impl ComponentArgs for SliceArgs {
+    fn connect_drivers(
+        self,
+        component: ComponentId,
+        wires: &mut WireList,
+    ) -> Result<(), AddComponentError> {
+        let input_wire = wires.get_mut(self.input).ok_or(InvalidWireIdError)?;
+        input_wire.add_driving(component);
+        Ok(())
+    }
+}
+
 impl Component for Slice {
-    type Args<'a> = ();
+    type Args<'a> = SliceArgs;
 
     fn new(
         args: Self::Args<'_>,
         wires: &mut WireList,
         output_states: &mut OutputStateAllocator,
     ) -> Result<Self, AddComponentError> {
-        todo!()
+        let input_wire = wires
+            .get(args.input)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let output_wire = wires
+            .get(args.output)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        let input_width = input_wire.bit_width();
+        let bit_width = output_wire.bit_width();
+        if bit_width > input_width {
+            return Err(AddComponentError::WireWidthIncompatible);
+        }
+        if ((args.offset as u32) + bit_width.get()) > input_width.get() {
+            return Err(AddComponentError::OffsetOutOfRange);
+        }
+
+        let input = input_wire.state_id();
+
+        let output_wire = wires
+            .get_mut(args.output)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        let output_state = output_states.alloc(bit_width)?;
+        output_wire.add_driver(output_state);
+
+        Ok(Self {
+            bit_width,
+            input_width,
+            input,
+            offset: args.offset,
+            output_state,
+            output_wire: args.output,
+        })
     }
 
     #[cfg(feature = "dot-export")]
     fn node_name(&self) -> Cow<'static, str> {
-        todo!()
+        let end_offset = (self.offset as u32) + self.bit_width.get() - 1;
+        format!("[{end_offset}:{}]", self.offset).into()
     }
 
     #[cfg(feature = "dot-export")]
     fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
-        todo!()
+        smallvec![(self.output_wire, "Out".into())]
     }
 
     #[cfg(feature = "dot-export")]
     fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
-        todo!()
+        smallvec![(self.input, "In".into())]
+    }
+
+    fn copy_into(&self, builder: &mut SimulatorBuilder, wire_map: &WireMap) -> AddComponentResult {
+        builder.add_component::<Self>(SliceArgs {
+            input: wire_map.input(self.input),
+            offset: self.offset,
+            output: wire_map.output(self.output_wire),
+        })
     }
 
     #[inline]
@@ -1172,9 +1780,23 @@ impl Component for Slice {
     fn update(
         &mut self,
         wire_states: WireStateView,
-        output_states: OutputStateViewMut,
+        mut output_states: OutputStateViewMut,
     ) -> inline_vec!(WireId) {
-        todo!()
+        let [input, _] = wire_states
+            .get(self.input, self.input_width)
+            .expect("invalid wire state ID");
+
+        let mut tmp_state = InlineLogicState::undefined(self.bit_width);
+        slice(tmp_state.borrow_mut(), input, self.offset as u32);
+
+        let [mut output] = output_states
+            .get_mut(self.output_state, self.bit_width)
+            .expect("invalid output state ID");
+
+        match output.copy_from(&tmp_state) {
+            CopyFromResult::Unchanged => smallvec![],
+            CopyFromResult::Changed => smallvec![self.output_wire],
+        }
     }
 }
 
@@ -1252,132 +1874,485 @@ impl Component for Mul {
     }
 }
 
-impl Component for LeftShift {
-    binary_gate_impl!("SHL");
-
-    fn update(
-        &mut self,
-        wire_states: WireStateView,
-        output_states: OutputStateViewMut,
-    ) -> inline_vec!(WireId) {
-        todo!()
-    }
-}
-
-impl Component for LogicalRightShift {
-    binary_gate_impl!("LSHR");
-
-    fn update(
-        &mut self,
-        wire_states: WireStateView,
-        output_states: OutputStateViewMut,
-    ) -> inline_vec!(WireId) {
-        todo!()
-    }
+#[derive(Clone, Copy)]
+pub(crate) struct MulSplitArgs {
+    pub(crate) input_a: WireId,
+    pub(crate) input_b: WireId,
+    pub(crate) low: WireId,
+    pub(crate) high: WireId,
+    pub(crate) signed: bool,
 }
 
-impl Component for ArithmeticRightShift {
-    binary_gate_impl!("ASHR");
-
-    fn update(
-        &mut self,
-        wire_states: WireStateView,
-        output_states: OutputStateViewMut,
-    ) -> inline_vec!(WireId) {
-        todo!()
+impl ComponentArgs for MulSplitArgs {
+    fn connect_drivers(
+        self,
+        component: ComponentId,
+        wires: &mut WireList,
+    ) -> Result<(), AddComponentError> {
+        let wire_a = wires.get_mut(self.input_a).ok_or(InvalidWireIdError)?;
+        wire_a.add_driving(component);
+        let wire_b = wires.get_mut(self.input_b).ok_or(InvalidWireIdError)?;
+        wire_b.add_driving(component);
+        Ok(())
     }
 }
 
-impl Component for HorizontalAnd {
-    type Args<'a> = ();
+impl Component for MulSplit {
+    type Args<'a> = MulSplitArgs;
 
     fn new(
         args: Self::Args<'_>,
         wires: &mut WireList,
         output_states: &mut OutputStateAllocator,
     ) -> Result<Self, AddComponentError> {
-        todo!()
+        let input_a_wire = wires
+            .get(args.input_a)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let input_b_wire = wires
+            .get(args.input_b)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let low_wire = wires
+            .get(args.low)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let high_wire = wires
+            .get(args.high)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        let bit_width = low_wire.bit_width();
+        if (input_a_wire.bit_width() != bit_width)
+            || (input_b_wire.bit_width() != bit_width)
+            || (high_wire.bit_width() != bit_width)
+        {
+            return Err(AddComponentError::WireWidthMismatch);
+        }
+
+        let input_a = input_a_wire.state_id();
+        let input_b = input_b_wire.state_id();
+
+        // The outputs are allocated back to back so they form a single output range
+        let low_state = output_states.alloc(bit_width)?;
+        let high_state = output_states.alloc(bit_width)?;
+
+        for (wire, state) in [(args.low, low_state), (args.high, high_state)] {
+            let wire = wires
+                .get_mut(wire)
+                .ok_or(AddComponentError::InvalidWireId)?;
+            wire.add_driver(state);
+        }
+
+        Ok(Self {
+            bit_width,
+            signed: args.signed,
+            input_a,
+            input_b,
+            low_state,
+            high_state,
+            low_wire: args.low,
+            high_wire: args.high,
+        })
     }
 
     #[cfg(feature = "dot-export")]
     fn node_name(&self) -> Cow<'static, str> {
-        todo!()
+        if self.signed {
+            "SMUL".into()
+        } else {
+            "UMUL".into()
+        }
     }
 
     #[cfg(feature = "dot-export")]
     fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
-        todo!()
+        smallvec![(self.low_wire, "Lo".into()), (self.high_wire, "Hi".into())]
     }
 
     #[cfg(feature = "dot-export")]
     fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
-        todo!()
+        smallvec![(self.input_a, "A".into()), (self.input_b, "B".into())]
+    }
+
+    fn copy_into(&self, builder: &mut SimulatorBuilder, wire_map: &WireMap) -> AddComponentResult {
+        builder.add_component::<Self>(MulSplitArgs {
+            input_a: wire_map.input(self.input_a),
+            input_b: wire_map.input(self.input_b),
+            low: wire_map.output(self.low_wire),
+            high: wire_map.output(self.high_wire),
+            signed: self.signed,
+        })
     }
 
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
-        (self.output_state, self.output_state, self.bit_width)
+        (self.low_state, self.high_state, self.bit_width)
     }
 
     fn update(
         &mut self,
         wire_states: WireStateView,
-        output_states: OutputStateViewMut,
+        mut output_states: OutputStateViewMut,
     ) -> inline_vec!(WireId) {
-        todo!()
+        let mut low = InlineLogicState::undefined(self.bit_width);
+        let mut high = InlineLogicState::undefined(self.bit_width);
+
+        let [input_a, _] = wire_states
+            .get(self.input_a, self.bit_width)
+            .expect("invalid wire state ID");
+        let [input_b, _] = wire_states
+            .get(self.input_b, self.bit_width)
+            .expect("invalid wire state ID");
+        mul_split(
+            low.borrow_mut(),
+            high.borrow_mut(),
+            input_a,
+            input_b,
+            self.signed,
+        );
+
+        let mut changed = smallvec![];
+        for (state, wire, value) in [
+            (self.low_state, self.low_wire, &low),
+            (self.high_state, self.high_wire, &high),
+        ] {
+            let [mut output] = output_states
+                .get_mut(state, self.bit_width)
+                .expect("invalid output state ID");
+
+            if let CopyFromResult::Changed = output.copy_from(value) {
+                changed.push(wire);
+            }
+        }
+
+        changed
     }
 }
 
-impl Component for HorizontalOr {
-    type Args<'a> = ();
+#[derive(Clone, Copy)]
+pub(crate) struct RotateThroughCarryArgs {
+    pub(crate) input: WireId,
+    pub(crate) amount: WireId,
+    pub(crate) carry_in: WireId,
+    pub(crate) output: WireId,
+    pub(crate) carry_out: WireId,
+    pub(crate) direction: RotateDirection,
+}
+
+impl ComponentArgs for RotateThroughCarryArgs {
+    fn connect_drivers(
+        self,
+        component: ComponentId,
+        wires: &mut WireList,
+    ) -> Result<(), AddComponentError> {
+        for input in [self.input, self.amount, self.carry_in] {
+            let wire = wires.get_mut(input).ok_or(InvalidWireIdError)?;
+            wire.add_driving(component);
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for RotateThroughCarry {
+    type Args<'a> = RotateThroughCarryArgs;
 
     fn new(
         args: Self::Args<'_>,
         wires: &mut WireList,
         output_states: &mut OutputStateAllocator,
     ) -> Result<Self, AddComponentError> {
-        todo!()
+        let input_wire = wires
+            .get(args.input)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let amount_wire = wires
+            .get(args.amount)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let carry_in_wire = wires
+            .get(args.carry_in)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let output_wire = wires
+            .get(args.output)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let carry_out_wire = wires
+            .get(args.carry_out)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        let bit_width = output_wire.bit_width();
+        if input_wire.bit_width() != bit_width {
+            return Err(AddComponentError::WireWidthMismatch);
+        }
+        if carry_in_wire.bit_width() != BitWidth::MIN {
+            return Err(AddComponentError::WireWidthIncompatible);
+        }
+        if carry_out_wire.bit_width() != BitWidth::MIN {
+            return Err(AddComponentError::WireWidthIncompatible);
+        }
+
+        let amount_width = amount_wire.bit_width();
+        let input = input_wire.state_id();
+        let amount = amount_wire.state_id();
+        let carry_in = carry_in_wire.state_id();
+
+        // The outputs are allocated back to back so they form a single output range
+        let output_state = output_states.alloc(bit_width)?;
+        let carry_out_state = output_states.alloc(BitWidth::MIN)?;
+
+        for (wire, state) in [
+            (args.output, output_state),
+            (args.carry_out, carry_out_state),
+        ] {
+            let wire = wires
+                .get_mut(wire)
+                .ok_or(AddComponentError::InvalidWireId)?;
+            wire.add_driver(state);
+        }
+
+        Ok(Self {
+            bit_width,
+            amount_width,
+            direction: args.direction,
+            input,
+            amount,
+            carry_in,
+            output_state,
+            carry_out_state,
+            output_wire: args.output,
+            carry_out_wire: args.carry_out,
+        })
     }
 
     #[cfg(feature = "dot-export")]
     fn node_name(&self) -> Cow<'static, str> {
-        todo!()
+        match self.direction {
+            RotateDirection::Left => "RCL".into(),
+            RotateDirection::Right => "RCR".into(),
+        }
     }
 
     #[cfg(feature = "dot-export")]
     fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
-        todo!()
+        smallvec![
+            (self.output_wire, "Out".into()),
+            (self.carry_out_wire, "Carry out".into()),
+        ]
     }
 
     #[cfg(feature = "dot-export")]
     fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
-        todo!()
+        smallvec![
+            (self.input, "In".into()),
+            (self.amount, "Amount".into()),
+            (self.carry_in, "Carry in".into()),
+        ]
+    }
+
+    fn copy_into(&self, builder: &mut SimulatorBuilder, wire_map: &WireMap) -> AddComponentResult {
+        builder.add_component::<Self>(RotateThroughCarryArgs {
+            input: wire_map.input(self.input),
+            amount: wire_map.input(self.amount),
+            carry_in: wire_map.input(self.carry_in),
+            output: wire_map.output(self.output_wire),
+            carry_out: wire_map.output(self.carry_out_wire),
+            direction: self.direction,
+        })
     }
 
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
-        (self.output_state, self.output_state, self.bit_width)
+        (self.output_state, self.carry_out_state, BitWidth::MIN)
     }
 
     fn update(
         &mut self,
         wire_states: WireStateView,
-        output_states: OutputStateViewMut,
+        mut output_states: OutputStateViewMut,
     ) -> inline_vec!(WireId) {
-        todo!()
-    }
-}
+        let [input, _] = wire_states
+            .get(self.input, self.bit_width)
+            .expect("invalid wire state ID");
+        let [amount, _] = wire_states
+            .get(self.amount, self.amount_width)
+            .expect("invalid wire state ID");
+        let [carry_in, _] = wire_states
+            .get(self.carry_in, BitWidth::MIN)
+            .expect("invalid wire state ID");
 
-impl Component for HorizontalXor {
-    type Args<'a> = ();
+        let mut rotated = InlineLogicState::undefined(self.bit_width);
+        let carry_out = rotate_through_carry(
+            rotated.borrow_mut(),
+            input,
+            carry_in.bit(0).expect("invalid wire width"),
+            amount,
+            self.direction,
+        );
 
-    fn new(
-        args: Self::Args<'_>,
-        wires: &mut WireList,
-        output_states: &mut OutputStateAllocator,
-    ) -> Result<Self, AddComponentError> {
-        todo!()
-    }
+        let mut changed = smallvec![];
+
+        let [mut output] = output_states
+            .get_mut(self.output_state, self.bit_width)
+            .expect("invalid output state ID");
+        if let CopyFromResult::Changed = output.copy_from(&rotated) {
+            changed.push(self.output_wire);
+        }
+
+        let [mut output] = output_states
+            .get_mut(self.carry_out_state, BitWidth::MIN)
+            .expect("invalid output state ID");
+        let mut carry = InlineLogicState::undefined(BitWidth::MIN);
+        match carry_out {
+            LogicBitState::Logic0 => carry.set_logic_0(),
+            LogicBitState::Logic1 => carry.set_logic_1(),
+            LogicBitState::HighZ => carry.set_high_z(),
+            LogicBitState::Undefined => carry.set_undefined(),
+        }
+        if let CopyFromResult::Changed = output.copy_from(&carry) {
+            changed.push(self.carry_out_wire);
+        }
+
+        changed
+    }
+}
+
+impl Component for LeftShift {
+    binary_gate_impl!("SHL");
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        todo!()
+    }
+}
+
+impl Component for LogicalRightShift {
+    binary_gate_impl!("LSHR");
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        todo!()
+    }
+}
+
+impl Component for ArithmeticRightShift {
+    binary_gate_impl!("ASHR");
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        todo!()
+    }
+}
+
+impl Component for HorizontalAnd {
+    type Args<'a> = ();
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn node_name(&self) -> Cow<'static, str> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
+        todo!()
+    }
+
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
+    #[inline]
+    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
+        (self.output_state, self.output_state, self.bit_width)
+    }
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        todo!()
+    }
+}
+
+impl Component for HorizontalOr {
+    type Args<'a> = ();
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn node_name(&self) -> Cow<'static, str> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
+        todo!()
+    }
+
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
+    #[inline]
+    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
+        (self.output_state, self.output_state, self.bit_width)
+    }
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        todo!()
+    }
+}
+
+impl Component for HorizontalXor {
+    type Args<'a> = ();
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
+        todo!()
+    }
 
     #[cfg(feature = "dot-export")]
     fn node_name(&self) -> Cow<'static, str> {
@@ -1394,6 +2369,14 @@ impl Component for HorizontalXor {
         todo!()
     }
 
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
         (self.output_state, self.output_state, self.bit_width)
@@ -1434,6 +2417,14 @@ impl Component for HorizontalNand {
         todo!()
     }
 
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
         (self.output_state, self.output_state, self.bit_width)
@@ -1474,6 +2465,14 @@ impl Component for HorizontalNor {
         todo!()
     }
 
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
         (self.output_state, self.output_state, self.bit_width)
@@ -1514,6 +2513,14 @@ impl Component for HorizontalXnor {
         todo!()
     }
 
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
         (self.output_state, self.output_state, self.bit_width)
@@ -1554,6 +2561,14 @@ impl Component for CompareEqual {
         todo!()
     }
 
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
         (self.output_state, self.output_state, self.bit_width)
@@ -1594,6 +2609,14 @@ impl Component for CompareNotEqual {
         todo!()
     }
 
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
         (self.output_state, self.output_state, self.bit_width)
@@ -1634,6 +2657,14 @@ impl Component for CompareLessThan {
         todo!()
     }
 
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
         (self.output_state, self.output_state, self.bit_width)
@@ -1674,6 +2705,14 @@ impl Component for CompareGreaterThan {
         todo!()
     }
 
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
         (self.output_state, self.output_state, self.bit_width)
@@ -1714,6 +2753,14 @@ impl Component for CompareLessThanOrEqual {
         todo!()
     }
 
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
         (self.output_state, self.output_state, self.bit_width)
@@ -1754,6 +2801,14 @@ impl Component for CompareGreaterThanOrEqual {
         todo!()
     }
 
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
         (self.output_state, self.output_state, self.bit_width)
@@ -1794,6 +2849,14 @@ impl Component for CompareLessThanSigned {
         todo!()
     }
 
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
         (self.output_state, self.output_state, self.bit_width)
@@ -1829,182 +2892,1544 @@ impl Component for CompareGreaterThanSigned {
         todo!()
     }
 
-    #[cfg(feature = "dot-export")]
-    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
-        todo!()
+    #[cfg(feature = "dot-export")]
+    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
+        todo!()
+    }
+
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
+    #[inline]
+    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
+        (self.output_state, self.output_state, self.bit_width)
+    }
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        todo!()
+    }
+}
+
+impl Component for CompareLessThanOrEqualSigned {
+    type Args<'a> = ();
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn node_name(&self) -> Cow<'static, str> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
+        todo!()
+    }
+
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
+    #[inline]
+    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
+        (self.output_state, self.output_state, self.bit_width)
+    }
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        todo!()
+    }
+}
+
+impl Component for CompareGreaterThanOrEqualSigned {
+    type Args<'a> = ();
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn node_name(&self) -> Cow<'static, str> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
+        todo!()
+    }
+
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
+    #[inline]
+    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
+        (self.output_state, self.output_state, self.bit_width)
+    }
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        todo!()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct MagnitudeCompareArgs {
+    pub(crate) input_a: WireId,
+    pub(crate) input_b: WireId,
+    pub(crate) less: WireId,
+    pub(crate) equal: WireId,
+    pub(crate) greater: WireId,
+    pub(crate) signed: bool,
+}
+
+impl ComponentArgs for MagnitudeCompareArgs {
+    fn connect_drivers(
+        self,
+        component: ComponentId,
+        wires: &mut WireList,
+    ) -> Result<(), AddComponentError> {
+        let wire_a = wires.get_mut(self.input_a).ok_or(InvalidWireIdError)?;
+        wire_a.add_driving(component);
+        let wire_b = wires.get_mut(self.input_b).ok_or(InvalidWireIdError)?;
+        wire_b.add_driving(component);
+        Ok(())
+    }
+}
+
+impl Component for MagnitudeCompare {
+    type Args<'a> = MagnitudeCompareArgs;
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
+        let input_a_wire = wires
+            .get(args.input_a)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let input_b_wire = wires
+            .get(args.input_b)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        let bit_width = input_a_wire.bit_width();
+        if input_b_wire.bit_width() != bit_width {
+            return Err(AddComponentError::WireWidthMismatch);
+        }
+
+        for output in [args.less, args.equal, args.greater] {
+            let output_wire = wires.get(output).ok_or(AddComponentError::InvalidWireId)?;
+            if output_wire.bit_width() != BitWidth::MIN {
+                return Err(AddComponentError::WireWidthIncompatible);
+            }
+        }
+
+        let input_a = input_a_wire.state_id();
+        let input_b = input_b_wire.state_id();
+
+        // The outputs are allocated back to back so they form a single output range
+        let less_state = output_states.alloc(BitWidth::MIN)?;
+        let equal_state = output_states.alloc(BitWidth::MIN)?;
+        let greater_state = output_states.alloc(BitWidth::MIN)?;
+
+        for (wire, state) in [
+            (args.less, less_state),
+            (args.equal, equal_state),
+            (args.greater, greater_state),
+        ] {
+            let wire = wires
+                .get_mut(wire)
+                .ok_or(AddComponentError::InvalidWireId)?;
+            wire.add_driver(state);
+        }
+
+        Ok(Self {
+            bit_width,
+            signed: args.signed,
+            input_a,
+            input_b,
+            less_state,
+            equal_state,
+            greater_state,
+            less_wire: args.less,
+            equal_wire: args.equal,
+            greater_wire: args.greater,
+        })
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn node_name(&self) -> Cow<'static, str> {
+        if self.signed {
+            "SCMP".into()
+        } else {
+            "UCMP".into()
+        }
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
+        smallvec![
+            (self.less_wire, "<".into()),
+            (self.equal_wire, "==".into()),
+            (self.greater_wire, ">".into()),
+        ]
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
+        smallvec![(self.input_a, "A".into()), (self.input_b, "B".into())]
+    }
+
+    fn copy_into(&self, builder: &mut SimulatorBuilder, wire_map: &WireMap) -> AddComponentResult {
+        builder.add_component::<Self>(MagnitudeCompareArgs {
+            input_a: wire_map.input(self.input_a),
+            input_b: wire_map.input(self.input_b),
+            less: wire_map.output(self.less_wire),
+            equal: wire_map.output(self.equal_wire),
+            greater: wire_map.output(self.greater_wire),
+            signed: self.signed,
+        })
+    }
+
+    #[inline]
+    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
+        (self.less_state, self.greater_state, BitWidth::MIN)
+    }
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        mut output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        let [input_a, _] = wire_states
+            .get(self.input_a, self.bit_width)
+            .expect("invalid wire state ID");
+        let [input_b, _] = wire_states
+            .get(self.input_b, self.bit_width)
+            .expect("invalid wire state ID");
+        let flags = magnitude_compare(input_a, input_b, self.signed);
+
+        let mut changed = smallvec![];
+        for (state, wire, flag) in izip!(
+            [self.less_state, self.equal_state, self.greater_state],
+            [self.less_wire, self.equal_wire, self.greater_wire],
+            flags,
+        ) {
+            let [mut output] = output_states
+                .get_mut(state, BitWidth::MIN)
+                .expect("invalid output state ID");
+
+            let mut value = InlineLogicState::undefined(BitWidth::MIN);
+            match flag {
+                LogicBitState::Logic0 => value.set_logic_0(),
+                LogicBitState::Logic1 => value.set_logic_1(),
+                LogicBitState::HighZ => value.set_high_z(),
+                LogicBitState::Undefined => value.set_undefined(),
+            }
+            if let CopyFromResult::Changed = output.copy_from(&value) {
+                changed.push(wire);
+            }
+        }
+
+        changed
+    }
+}
+
+impl Component for ZeroExtend {
+    type Args<'a> = ();
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn node_name(&self) -> Cow<'static, str> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
+        todo!()
+    }
+
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
+    #[inline]
+    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
+        (self.output_state, self.output_state, self.bit_width)
+    }
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        todo!()
+    }
+}
+
+impl Component for SignExtend {
+    type Args<'a> = ();
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn node_name(&self) -> Cow<'static, str> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
+        todo!()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
+        todo!()
+    }
+
+    fn copy_into(
+        &self,
+        _builder: &mut SimulatorBuilder,
+        _wire_map: &WireMap,
+    ) -> AddComponentResult {
+        todo!()
+    }
+
+    #[inline]
+    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
+        (self.output_state, self.output_state, self.bit_width)
+    }
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        todo!()
+    }
+}
+
+#[derive(Clone)]
+struct ClockTrigger {
+    prev: Option<bool>,
+    polarity: ClockPolarity,
+}
+
+impl ClockTrigger {
+    #[inline]
+    const fn new(polarity: ClockPolarity) -> Self {
+        Self {
+            prev: None,
+            polarity,
+        }
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.prev = None;
+    }
+
+    #[inline]
+    fn update(&mut self, current: LogicBitState) -> bool {
+        let current = match current {
+            LogicBitState::HighZ | LogicBitState::Undefined => self.prev,
+            LogicBitState::Logic0 => Some(false),
+            LogicBitState::Logic1 => Some(true),
+        };
+
+        let edge = (self.prev == Some(self.polarity.inactive_state()))
+            && (current == Some(self.polarity.active_state()));
+
+        self.prev = current;
+        edge
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct EdgeDetectorArgs {
+    pub(crate) input: WireId,
+    pub(crate) output: WireId,
+    pub(crate) clock: WireId,
+    pub(crate) clock_polarity: ClockPolarity,
+    pub(crate) edge: EdgeKind,
+}
+
+impl ComponentArgs for EdgeDetectorArgs {
+    fn connect_drivers(
+        self,
+        component: ComponentId,
+        wires: &mut WireList,
+    ) -> Result<(), AddComponentError> {
+        let input_wire = wires.get_mut(self.input).ok_or(InvalidWireIdError)?;
+        input_wire.add_driving(component);
+        let clock_wire = wires.get_mut(self.clock).ok_or(InvalidWireIdError)?;
+        clock_wire.add_driving(component);
+        Ok(())
+    }
+}
+
+impl Component for EdgeDetector {
+    type Args<'a> = EdgeDetectorArgs;
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
+        let output_wire = wires
+            .get(args.output)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let input_wire = wires
+            .get(args.input)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let clock_wire = wires
+            .get(args.clock)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        if output_wire.bit_width() != BitWidth::MIN {
+            return Err(AddComponentError::WireWidthIncompatible);
+        }
+        if input_wire.bit_width() != BitWidth::MIN {
+            return Err(AddComponentError::WireWidthIncompatible);
+        }
+        if clock_wire.bit_width() != BitWidth::MIN {
+            return Err(AddComponentError::WireWidthIncompatible);
+        }
+
+        let input = input_wire.state_id();
+        let clock = clock_wire.state_id();
+
+        let output_wire = wires
+            .get_mut(args.output)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        let output_state = output_states.alloc(BitWidth::MIN)?;
+        output_wire.add_driver(output_state);
+
+        Ok(Self {
+            input,
+            clock,
+            clock_trigger: ClockTrigger::new(args.clock_polarity),
+            edge: args.edge,
+            prev_input: None,
+            pulse: false,
+            output_state,
+            output_wire: args.output,
+        })
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn node_name(&self) -> Cow<'static, str> {
+        "Edge Detector".into()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
+        smallvec![(self.output_wire, "Out".into())]
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
+        smallvec![(self.input, "In".into()), (self.clock, "Clk".into())]
+    }
+
+    fn copy_into(&self, builder: &mut SimulatorBuilder, wire_map: &WireMap) -> AddComponentResult {
+        builder.add_component::<Self>(EdgeDetectorArgs {
+            input: wire_map.input(self.input),
+            output: wire_map.output(self.output_wire),
+            clock: wire_map.input(self.clock),
+            clock_polarity: self.clock_trigger.polarity,
+            edge: self.edge,
+        })
+    }
+
+    #[inline]
+    fn clock(&self) -> Option<WireStateId> {
+        Some(self.clock)
+    }
+
+    #[inline]
+    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
+        (self.output_state, self.output_state, BitWidth::MIN)
+    }
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        mut output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        let [input, _] = wire_states
+            .get(self.input, BitWidth::MIN)
+            .expect("invalid wire state ID");
+        let [clock, _] = wire_states
+            .get(self.clock, BitWidth::MIN)
+            .expect("invalid wire state ID");
+
+        if self
+            .clock_trigger
+            .update(clock.bit(0).expect("invalid wire width"))
+        {
+            let current = match input.bit(0).expect("invalid wire width") {
+                LogicBitState::Logic0 => Some(false),
+                LogicBitState::Logic1 => Some(true),
+                LogicBitState::HighZ | LogicBitState::Undefined => None,
+            };
+
+            self.pulse = match (self.prev_input, current) {
+                (Some(false), Some(true)) => {
+                    matches!(self.edge, EdgeKind::Rising | EdgeKind::Either)
+                }
+                (Some(true), Some(false)) => {
+                    matches!(self.edge, EdgeKind::Falling | EdgeKind::Either)
+                }
+                _ => false,
+            };
+            self.prev_input = current;
+        }
+
+        let tmp_state = if self.pulse {
+            InlineLogicState::logic_1(BitWidth::MIN)
+        } else {
+            InlineLogicState::logic_0(BitWidth::MIN)
+        };
+
+        let [mut output] = output_states
+            .get_mut(self.output_state, BitWidth::MIN)
+            .expect("invalid output state ID");
+
+        match output.copy_from(&tmp_state) {
+            CopyFromResult::Unchanged => smallvec![],
+            CopyFromResult::Changed => smallvec![self.output_wire],
+        }
+    }
+
+    fn reset(&mut self) {
+        self.clock_trigger.reset();
+        self.prev_input = None;
+        self.pulse = false;
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct FifoArgs {
+    pub(crate) data_in: WireId,
+    pub(crate) data_out: WireId,
+    pub(crate) push: WireId,
+    pub(crate) pop: WireId,
+    pub(crate) clock: WireId,
+    pub(crate) full: WireId,
+    pub(crate) empty: WireId,
+    pub(crate) depth: NonZeroUsize,
+    pub(crate) clock_polarity: ClockPolarity,
+}
+
+impl ComponentArgs for FifoArgs {
+    fn connect_drivers(
+        self,
+        component: ComponentId,
+        wires: &mut WireList,
+    ) -> Result<(), AddComponentError> {
+        for input in [self.data_in, self.push, self.pop, self.clock] {
+            let wire = wires.get_mut(input).ok_or(InvalidWireIdError)?;
+            wire.add_driving(component);
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for Fifo {
+    type Args<'a> = FifoArgs;
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
+        let data_in_wire = wires
+            .get(args.data_in)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let data_out_wire = wires
+            .get(args.data_out)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        if data_in_wire.bit_width() != data_out_wire.bit_width() {
+            return Err(AddComponentError::WireWidthMismatch);
+        }
+
+        let bit_width = data_out_wire.bit_width();
+        let data_in = data_in_wire.state_id();
+
+        let mut control_states = [WireStateId::from_bits(0); 3];
+        for (state, control) in control_states
+            .iter_mut()
+            .zip([args.push, args.pop, args.clock])
+        {
+            let wire = wires.get(control).ok_or(AddComponentError::InvalidWireId)?;
+            if wire.bit_width() != BitWidth::MIN {
+                return Err(AddComponentError::WireWidthIncompatible);
+            }
+
+            *state = wire.state_id();
+        }
+        let [push, pop, clock] = control_states;
+
+        for flag in [args.full, args.empty] {
+            let wire = wires.get(flag).ok_or(AddComponentError::InvalidWireId)?;
+            if wire.bit_width() != BitWidth::MIN {
+                return Err(AddComponentError::WireWidthIncompatible);
+            }
+        }
+
+        // The outputs are allocated back to back so they form a single output range
+        let data_out_state = output_states.alloc(bit_width)?;
+        let full_state = output_states.alloc(BitWidth::MIN)?;
+        let empty_state = output_states.alloc(BitWidth::MIN)?;
+
+        for (wire, state) in [
+            (args.data_out, data_out_state),
+            (args.full, full_state),
+            (args.empty, empty_state),
+        ] {
+            let wire = wires
+                .get_mut(wire)
+                .ok_or(AddComponentError::InvalidWireId)?;
+            wire.add_driver(state);
+        }
+
+        Ok(Self {
+            bit_width,
+            data_in,
+            push,
+            pop,
+            clock,
+            clock_trigger: ClockTrigger::new(args.clock_polarity),
+            depth: args.depth,
+            queue: VecDeque::new(),
+            data_out_state,
+            full_state,
+            empty_state,
+            data_out_wire: args.data_out,
+            full_wire: args.full,
+            empty_wire: args.empty,
+        })
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn node_name(&self) -> Cow<'static, str> {
+        "FIFO".into()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
+        smallvec![
+            (self.data_out_wire, "Data out".into()),
+            (self.full_wire, "Full".into()),
+            (self.empty_wire, "Empty".into()),
+        ]
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
+        smallvec![
+            (self.data_in, "Data in".into()),
+            (self.push, "Push".into()),
+            (self.pop, "Pop".into()),
+            (self.clock, "Clk".into()),
+        ]
+    }
+
+    fn copy_into(&self, builder: &mut SimulatorBuilder, wire_map: &WireMap) -> AddComponentResult {
+        builder.add_component::<Self>(FifoArgs {
+            data_in: wire_map.input(self.data_in),
+            data_out: wire_map.output(self.data_out_wire),
+            push: wire_map.input(self.push),
+            pop: wire_map.input(self.pop),
+            clock: wire_map.input(self.clock),
+            full: wire_map.output(self.full_wire),
+            empty: wire_map.output(self.empty_wire),
+            depth: self.depth,
+            clock_polarity: self.clock_trigger.polarity,
+        })
+    }
+
+    #[inline]
+    fn clock(&self) -> Option<WireStateId> {
+        Some(self.clock)
+    }
+
+    #[inline]
+    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
+        (self.data_out_state, self.empty_state, BitWidth::MIN)
+    }
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        mut output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        let [clock, _] = wire_states
+            .get(self.clock, BitWidth::MIN)
+            .expect("invalid wire state ID");
+
+        if self
+            .clock_trigger
+            .update(clock.bit(0).expect("invalid wire width"))
+        {
+            let [push, _] = wire_states
+                .get(self.push, BitWidth::MIN)
+                .expect("invalid wire state ID");
+            let [pop, _] = wire_states
+                .get(self.pop, BitWidth::MIN)
+                .expect("invalid wire state ID");
+
+            // Popping first allows a full FIFO to accept a push in the same cycle
+            if pop.bit(0) == Some(LogicBitState::Logic1) {
+                self.queue.pop_front();
+            }
+
+            if (push.bit(0) == Some(LogicBitState::Logic1)) && (self.queue.len() < self.depth.get())
+            {
+                let [data_in, _] = wire_states
+                    .get(self.data_in, self.bit_width)
+                    .expect("invalid wire state ID");
+
+                let mut value = InlineLogicState::undefined(self.bit_width);
+                unary_op(value.borrow_mut(), data_in, high_z_to_undefined);
+                self.queue.push_back(value);
+            }
+        }
+
+        let mut changed = smallvec![];
+
+        let [mut data_out] = output_states
+            .get_mut(self.data_out_state, self.bit_width)
+            .expect("invalid output state ID");
+        let data_out_result = match self.queue.front() {
+            Some(front) => data_out.copy_from(front),
+            None => data_out.copy_from(&InlineLogicState::undefined(self.bit_width)),
+        };
+        if let CopyFromResult::Changed = data_out_result {
+            changed.push(self.data_out_wire);
+        }
+
+        for (state, wire, value) in [
+            (
+                self.full_state,
+                self.full_wire,
+                self.queue.len() == self.depth.get(),
+            ),
+            (self.empty_state, self.empty_wire, self.queue.is_empty()),
+        ] {
+            let [mut output] = output_states
+                .get_mut(state, BitWidth::MIN)
+                .expect("invalid output state ID");
+
+            let value = if value {
+                InlineLogicState::logic_1(BitWidth::MIN)
+            } else {
+                InlineLogicState::logic_0(BitWidth::MIN)
+            };
+
+            if let CopyFromResult::Changed = output.copy_from(&value) {
+                changed.push(wire);
+            }
+        }
+
+        changed
+    }
+
+    fn reset(&mut self) {
+        self.clock_trigger.reset();
+        self.queue.clear();
+    }
+
+    fn memory(&self) -> Option<Vec<InlineLogicState>> {
+        Some(self.queue.iter().cloned().collect())
+    }
+
+    fn restore_memory(&mut self, memory: &[InlineLogicState]) {
+        debug_assert!(memory.len() <= self.depth.get(), "invalid memory length");
+
+        self.queue.clear();
+        self.queue.extend(memory.iter().cloned());
+    }
+
+    fn memory_layout(&self) -> Option<(BitWidth, RangeInclusive<usize>)> {
+        Some((self.bit_width, 0..=self.depth.get()))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct DelayArgs {
+    pub(crate) input: WireId,
+    pub(crate) output: WireId,
+    pub(crate) clock: WireId,
+    pub(crate) clock_polarity: ClockPolarity,
+}
+
+impl ComponentArgs for DelayArgs {
+    fn connect_drivers(
+        self,
+        component: ComponentId,
+        wires: &mut WireList,
+    ) -> Result<(), AddComponentError> {
+        for input in [self.input, self.clock] {
+            let wire = wires.get_mut(input).ok_or(InvalidWireIdError)?;
+            wire.add_driving(component);
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for Delay {
+    type Args<'a> = DelayArgs;
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
+        let output_wire = wires
+            .get(args.output)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let input_wire = wires
+            .get(args.input)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let clock_wire = wires
+            .get(args.clock)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        if input_wire.bit_width() != output_wire.bit_width() {
+            return Err(AddComponentError::WireWidthMismatch);
+        }
+        if clock_wire.bit_width() != BitWidth::MIN {
+            return Err(AddComponentError::WireWidthIncompatible);
+        }
+
+        let bit_width = output_wire.bit_width();
+        let input = input_wire.state_id();
+        let clock = clock_wire.state_id();
+
+        let output_wire = wires
+            .get_mut(args.output)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        let output_state = output_states.alloc(bit_width)?;
+        output_wire.add_driver(output_state);
+
+        Ok(Self {
+            bit_width,
+            input,
+            clock,
+            clock_trigger: ClockTrigger::new(args.clock_polarity),
+            value: InlineLogicState::undefined(bit_width),
+            output_state,
+            output_wire: args.output,
+        })
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn node_name(&self) -> Cow<'static, str> {
+        "Delay".into()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
+        smallvec![(self.output_wire, "Out".into())]
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
+        smallvec![(self.input, "In".into()), (self.clock, "Clk".into())]
+    }
+
+    fn copy_into(&self, builder: &mut SimulatorBuilder, wire_map: &WireMap) -> AddComponentResult {
+        builder.add_component::<Self>(DelayArgs {
+            input: wire_map.input(self.input),
+            output: wire_map.output(self.output_wire),
+            clock: wire_map.input(self.clock),
+            clock_polarity: self.clock_trigger.polarity,
+        })
+    }
+
+    #[inline]
+    fn clock(&self) -> Option<WireStateId> {
+        Some(self.clock)
+    }
+
+    #[inline]
+    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
+        (self.output_state, self.output_state, self.bit_width)
+    }
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        mut output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        let [clock, _] = wire_states
+            .get(self.clock, BitWidth::MIN)
+            .expect("invalid wire state ID");
+
+        if self
+            .clock_trigger
+            .update(clock.bit(0).expect("invalid wire width"))
+        {
+            let [input, _] = wire_states
+                .get(self.input, self.bit_width)
+                .expect("invalid wire state ID");
+            unary_op(self.value.borrow_mut(), input, high_z_to_undefined);
+        }
+
+        let [mut output] = output_states
+            .get_mut(self.output_state, self.bit_width)
+            .expect("invalid output state ID");
+
+        match output.copy_from(&self.value) {
+            CopyFromResult::Unchanged => smallvec![],
+            CopyFromResult::Changed => smallvec![self.output_wire],
+        }
+    }
+
+    fn reset(&mut self) {
+        self.clock_trigger.reset();
+        self.value = InlineLogicState::undefined(self.bit_width);
+    }
+
+    fn memory(&self) -> Option<Vec<InlineLogicState>> {
+        Some(vec![self.value.clone()])
+    }
+
+    fn restore_memory(&mut self, memory: &[InlineLogicState]) {
+        debug_assert_eq!(memory.len(), 1, "invalid memory length");
+
+        if let [value] = memory {
+            self.value = value.clone();
+        }
+    }
+
+    fn memory_layout(&self) -> Option<(BitWidth, RangeInclusive<usize>)> {
+        Some((self.bit_width, 1..=1))
+    }
+
+    #[inline]
+    fn register_value(&self) -> Option<LogicStateRef<'_>> {
+        Some(self.value.borrow())
+    }
+
+    fn set_register_value(&mut self, value: LogicStateRef<'_>) {
+        let _ = self.value.copy_from(value);
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct RegisterArgs {
+    pub(crate) data_in: WireId,
+    pub(crate) data_out: WireId,
+    pub(crate) enable: WireId,
+    pub(crate) enable_polarity: EnablePolarity,
+    pub(crate) clock: WireId,
+    pub(crate) clock_polarity: ClockPolarity,
+}
+
+impl ComponentArgs for RegisterArgs {
+    fn connect_drivers(
+        self,
+        component: ComponentId,
+        wires: &mut WireList,
+    ) -> Result<(), AddComponentError> {
+        for input in [self.data_in, self.enable, self.clock] {
+            let wire = wires.get_mut(input).ok_or(InvalidWireIdError)?;
+            wire.add_driving(component);
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for Register {
+    type Args<'a> = RegisterArgs;
+
+    fn new(
+        args: Self::Args<'_>,
+        wires: &mut WireList,
+        output_states: &mut OutputStateAllocator,
+    ) -> Result<Self, AddComponentError> {
+        let data_in_wire = wires
+            .get(args.data_in)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let data_out_wire = wires
+            .get(args.data_out)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let enable_wire = wires
+            .get(args.enable)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let clock_wire = wires
+            .get(args.clock)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        if data_in_wire.bit_width() != data_out_wire.bit_width() {
+            return Err(AddComponentError::WireWidthMismatch);
+        }
+        if enable_wire.bit_width() != BitWidth::MIN {
+            return Err(AddComponentError::WireWidthIncompatible);
+        }
+        if clock_wire.bit_width() != BitWidth::MIN {
+            return Err(AddComponentError::WireWidthIncompatible);
+        }
+
+        let bit_width = data_out_wire.bit_width();
+        let data_in = data_in_wire.state_id();
+        let enable = enable_wire.state_id();
+        let clock = clock_wire.state_id();
+
+        let data_out_wire = wires
+            .get_mut(args.data_out)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        let output_state = output_states.alloc(bit_width)?;
+        data_out_wire.add_driver(output_state);
+
+        Ok(Self {
+            bit_width,
+            data_in,
+            enable,
+            enable_polarity: args.enable_polarity,
+            clock,
+            clock_trigger: ClockTrigger::new(args.clock_polarity),
+            value: InlineLogicState::undefined(bit_width),
+            output_state,
+            output_wire: args.data_out,
+        })
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn node_name(&self) -> Cow<'static, str> {
+        "Register".into()
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
+        smallvec![(self.output_wire, "Data out".into())]
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
+        smallvec![
+            (self.data_in, "Data in".into()),
+            (self.enable, "En".into()),
+            (self.clock, "Clk".into()),
+        ]
+    }
+
+    fn copy_into(&self, builder: &mut SimulatorBuilder, wire_map: &WireMap) -> AddComponentResult {
+        builder.add_component::<Self>(RegisterArgs {
+            data_in: wire_map.input(self.data_in),
+            data_out: wire_map.output(self.output_wire),
+            enable: wire_map.input(self.enable),
+            enable_polarity: self.enable_polarity,
+            clock: wire_map.input(self.clock),
+            clock_polarity: self.clock_trigger.polarity,
+        })
+    }
+
+    #[inline]
+    fn clock(&self) -> Option<WireStateId> {
+        Some(self.clock)
+    }
+
+    #[inline]
+    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
+        (self.output_state, self.output_state, self.bit_width)
+    }
+
+    fn update(
+        &mut self,
+        wire_states: WireStateView,
+        mut output_states: OutputStateViewMut,
+    ) -> inline_vec!(WireId) {
+        let [clock, _] = wire_states
+            .get(self.clock, BitWidth::MIN)
+            .expect("invalid wire state ID");
+
+        if self
+            .clock_trigger
+            .update(clock.bit(0).expect("invalid wire width"))
+        {
+            let [enable, _] = wire_states
+                .get(self.enable, BitWidth::MIN)
+                .expect("invalid wire state ID");
+
+            match enable.bit(0).expect("invalid wire width").to_bool() {
+                None => self.value.set_undefined(),
+                Some(enable) if enable == self.enable_polarity.active_state() => {
+                    let [data_in, _] = wire_states
+                        .get(self.data_in, self.bit_width)
+                        .expect("invalid wire state ID");
+                    unary_op(self.value.borrow_mut(), data_in, high_z_to_undefined);
+                }
+                Some(_) => (),
+            }
+        }
+
+        let [mut output] = output_states
+            .get_mut(self.output_state, self.bit_width)
+            .expect("invalid output state ID");
+
+        match output.copy_from(&self.value) {
+            CopyFromResult::Unchanged => smallvec![],
+            CopyFromResult::Changed => smallvec![self.output_wire],
+        }
     }
 
-    #[inline]
-    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
-        (self.output_state, self.output_state, self.bit_width)
+    fn reset(&mut self) {
+        self.clock_trigger.reset();
+        self.value = InlineLogicState::undefined(self.bit_width);
     }
 
-    fn update(
-        &mut self,
-        wire_states: WireStateView,
-        output_states: OutputStateViewMut,
-    ) -> inline_vec!(WireId) {
-        todo!()
+    fn memory(&self) -> Option<Vec<InlineLogicState>> {
+        Some(vec![self.value.clone()])
     }
-}
 
-impl Component for CompareLessThanOrEqualSigned {
-    type Args<'a> = ();
+    fn restore_memory(&mut self, memory: &[InlineLogicState]) {
+        debug_assert_eq!(memory.len(), 1, "invalid memory length");
 
-    fn new(
-        args: Self::Args<'_>,
-        wires: &mut WireList,
-        output_states: &mut OutputStateAllocator,
-    ) -> Result<Self, AddComponentError> {
-        todo!()
+        if let [value] = memory {
+            self.value = value.clone();
+        }
     }
 
-    #[cfg(feature = "dot-export")]
-    fn node_name(&self) -> Cow<'static, str> {
-        todo!()
+    fn memory_layout(&self) -> Option<(BitWidth, RangeInclusive<usize>)> {
+        Some((self.bit_width, 1..=1))
     }
 
-    #[cfg(feature = "dot-export")]
-    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
-        todo!()
+    #[inline]
+    fn register_value(&self) -> Option<LogicStateRef<'_>> {
+        Some(self.value.borrow())
     }
 
-    #[cfg(feature = "dot-export")]
-    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
-        todo!()
+    fn set_register_value(&mut self, value: LogicStateRef<'_>) {
+        let _ = self.value.copy_from(value);
     }
+}
 
-    #[inline]
-    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
-        (self.output_state, self.output_state, self.bit_width)
-    }
+#[derive(Clone, Copy)]
+pub(crate) struct RamArgs {
+    pub(crate) write_addr: WireId,
+    pub(crate) data_in: WireId,
+    pub(crate) read_addr: WireId,
+    pub(crate) data_out: WireId,
+    pub(crate) write: WireId,
+    pub(crate) write_polarity: EnablePolarity,
+    pub(crate) clock: WireId,
+    pub(crate) clock_polarity: ClockPolarity,
+}
 
-    fn update(
-        &mut self,
-        wire_states: WireStateView,
-        output_states: OutputStateViewMut,
-    ) -> inline_vec!(WireId) {
-        todo!()
+impl ComponentArgs for RamArgs {
+    fn connect_drivers(
+        self,
+        component: ComponentId,
+        wires: &mut WireList,
+    ) -> Result<(), AddComponentError> {
+        for input in [
+            self.write_addr,
+            self.data_in,
+            self.read_addr,
+            self.write,
+            self.clock,
+        ] {
+            let wire = wires.get_mut(input).ok_or(InvalidWireIdError)?;
+            wire.add_driving(component);
+        }
+
+        Ok(())
     }
 }
 
-impl Component for CompareGreaterThanOrEqualSigned {
-    type Args<'a> = ();
+impl Component for Ram {
+    type Args<'a> = RamArgs;
 
     fn new(
         args: Self::Args<'_>,
         wires: &mut WireList,
         output_states: &mut OutputStateAllocator,
     ) -> Result<Self, AddComponentError> {
-        todo!()
+        let write_addr_wire = wires
+            .get(args.write_addr)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let data_in_wire = wires
+            .get(args.data_in)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let read_addr_wire = wires
+            .get(args.read_addr)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let data_out_wire = wires
+            .get(args.data_out)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let write_wire = wires
+            .get(args.write)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let clock_wire = wires
+            .get(args.clock)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        if write_addr_wire.bit_width() != read_addr_wire.bit_width() {
+            return Err(AddComponentError::WireWidthMismatch);
+        }
+        if data_in_wire.bit_width() != data_out_wire.bit_width() {
+            return Err(AddComponentError::WireWidthMismatch);
+        }
+        if write_wire.bit_width() != BitWidth::MIN {
+            return Err(AddComponentError::WireWidthIncompatible);
+        }
+        if clock_wire.bit_width() != BitWidth::MIN {
+            return Err(AddComponentError::WireWidthIncompatible);
+        }
+
+        let addr_width = read_addr_wire.bit_width();
+        let data_width = data_out_wire.bit_width();
+        let len = 1usize
+            .checked_shl(addr_width.get())
+            .ok_or(AddComponentError::MemoryTooLarge)?;
+
+        let write_addr = write_addr_wire.state_id();
+        let data_in = data_in_wire.state_id();
+        let read_addr = read_addr_wire.state_id();
+        let write = write_wire.state_id();
+        let clock = clock_wire.state_id();
+
+        let data_out_wire = wires
+            .get_mut(args.data_out)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        let output_state = output_states.alloc(data_width)?;
+        data_out_wire.add_driver(output_state);
+
+        Ok(Self {
+            addr_width,
+            data_width,
+            write_addr,
+            data_in,
+            read_addr,
+            write,
+            write_polarity: args.write_polarity,
+            clock,
+            clock_trigger: ClockTrigger::new(args.clock_polarity),
+            memory: vec![InlineLogicState::undefined(data_width); len].into_boxed_slice(),
+            output_state,
+            output_wire: args.data_out,
+        })
     }
 
     #[cfg(feature = "dot-export")]
     fn node_name(&self) -> Cow<'static, str> {
-        todo!()
+        "RAM".into()
     }
 
     #[cfg(feature = "dot-export")]
     fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
-        todo!()
+        smallvec![(self.output_wire, "Data out".into())]
     }
 
     #[cfg(feature = "dot-export")]
     fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
-        todo!()
+        smallvec![
+            (self.write_addr, "Write addr".into()),
+            (self.data_in, "Data in".into()),
+            (self.read_addr, "Read addr".into()),
+            (self.write, "Write".into()),
+            (self.clock, "Clk".into()),
+        ]
+    }
+
+    fn copy_into(&self, builder: &mut SimulatorBuilder, wire_map: &WireMap) -> AddComponentResult {
+        builder.add_component::<Self>(RamArgs {
+            write_addr: wire_map.input(self.write_addr),
+            data_in: wire_map.input(self.data_in),
+            read_addr: wire_map.input(self.read_addr),
+            data_out: wire_map.output(self.output_wire),
+            write: wire_map.input(self.write),
+            write_polarity: self.write_polarity,
+            clock: wire_map.input(self.clock),
+            clock_polarity: self.clock_trigger.polarity,
+        })
+    }
+
+    #[inline]
+    fn clock(&self) -> Option<WireStateId> {
+        Some(self.clock)
     }
 
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
-        (self.output_state, self.output_state, self.bit_width)
+        (self.output_state, self.output_state, self.data_width)
     }
 
     fn update(
         &mut self,
         wire_states: WireStateView,
-        output_states: OutputStateViewMut,
+        mut output_states: OutputStateViewMut,
     ) -> inline_vec!(WireId) {
-        todo!()
-    }
-}
+        let [clock, _] = wire_states
+            .get(self.clock, BitWidth::MIN)
+            .expect("invalid wire state ID");
 
-impl Component for ZeroExtend {
-    type Args<'a> = ();
+        if self
+            .clock_trigger
+            .update(clock.bit(0).expect("invalid wire width"))
+        {
+            let [write_addr, _] = wire_states
+                .get(self.write_addr, self.addr_width)
+                .expect("invalid wire state ID");
 
-    fn new(
-        args: Self::Args<'_>,
-        wires: &mut WireList,
-        output_states: &mut OutputStateAllocator,
-    ) -> Result<Self, AddComponentError> {
-        todo!()
+            // There is nothing sensible we can do with an unknown address,
+            // in a real circuit a random entry would be overwritten
+            if let Some(entry) = to_address(write_addr).and_then(|addr| self.memory.get_mut(addr)) {
+                let [write, _] = wire_states
+                    .get(self.write, BitWidth::MIN)
+                    .expect("invalid wire state ID");
+
+                match write.bit(0).expect("invalid wire width").to_bool() {
+                    None => entry.set_undefined(),
+                    Some(write) if write == self.write_polarity.active_state() => {
+                        let [data_in, _] = wire_states
+                            .get(self.data_in, self.data_width)
+                            .expect("invalid wire state ID");
+                        unary_op(entry.borrow_mut(), data_in, high_z_to_undefined);
+                    }
+                    Some(_) => (),
+                }
+            }
+        }
+
+        let [read_addr, _] = wire_states
+            .get(self.read_addr, self.addr_width)
+            .expect("invalid wire state ID");
+
+        let [mut output] = output_states
+            .get_mut(self.output_state, self.data_width)
+            .expect("invalid output state ID");
+
+        let result = match to_address(read_addr).and_then(|addr| self.memory.get(addr)) {
+            Some(entry) => output.copy_from(entry),
+            None => output.copy_from(&InlineLogicState::undefined(self.data_width)),
+        };
+
+        match result {
+            CopyFromResult::Unchanged => smallvec![],
+            CopyFromResult::Changed => smallvec![self.output_wire],
+        }
     }
 
-    #[cfg(feature = "dot-export")]
-    fn node_name(&self) -> Cow<'static, str> {
-        todo!()
+    fn reset(&mut self) {
+        self.clock_trigger.reset();
+        self.memory
+            .fill(InlineLogicState::undefined(self.data_width));
     }
 
-    #[cfg(feature = "dot-export")]
-    fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
-        todo!()
+    fn memory(&self) -> Option<Vec<InlineLogicState>> {
+        Some(self.memory.to_vec())
     }
 
-    #[cfg(feature = "dot-export")]
-    fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
-        todo!()
+    fn restore_memory(&mut self, memory: &[InlineLogicState]) {
+        debug_assert_eq!(memory.len(), self.memory.len(), "invalid memory length");
+
+        for (entry, value) in self.memory.iter_mut().zip(memory) {
+            entry.clone_from(value);
+        }
     }
 
-    #[inline]
-    fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
-        (self.output_state, self.output_state, self.bit_width)
+    fn memory_layout(&self) -> Option<(BitWidth, RangeInclusive<usize>)> {
+        Some((self.data_width, self.memory.len()..=self.memory.len()))
     }
+}
 
-    fn update(
-        &mut self,
-        wire_states: WireStateView,
-        output_states: OutputStateViewMut,
-    ) -> inline_vec!(WireId) {
-        todo!()
+#[derive(Clone, Copy)]
+pub(crate) struct RomArgs<'a> {
+    pub(crate) addr: WireId,
+    pub(crate) data: WireId,
+    pub(crate) contents: &'a Arc<[InlineLogicState]>,
+}
+
+impl ComponentArgs for RomArgs<'_> {
+    fn connect_drivers(
+        self,
+        component: ComponentId,
+        wires: &mut WireList,
+    ) -> Result<(), AddComponentError> {
+        let addr_wire = wires.get_mut(self.addr).ok_or(InvalidWireIdError)?;
+        addr_wire.add_driving(component);
+        Ok(())
     }
 }
 
-impl Component for SignExtend {
-    type Args<'a> = ();
+impl Component for Rom {
+    type Args<'a> = RomArgs<'a>;
 
     fn new(
         args: Self::Args<'_>,
         wires: &mut WireList,
         output_states: &mut OutputStateAllocator,
     ) -> Result<Self, AddComponentError> {
-        todo!()
+        let addr_wire = wires
+            .get(args.addr)
+            .ok_or(AddComponentError::InvalidWireId)?;
+        let data_wire = wires
+            .get(args.data)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        let addr_width = addr_wire.bit_width();
+        let data_width = data_wire.bit_width();
+        if args
+            .contents
+            .iter()
+            .any(|entry| entry.bit_width() != data_width)
+        {
+            return Err(AddComponentError::WireWidthMismatch);
+        }
+
+        // Entries past the end of the contents read as undefined, so only more entries than addresses are rejected
+        if let Some(max_len) = 1usize.checked_shl(addr_width.get()) {
+            if args.contents.len() > max_len {
+                return Err(AddComponentError::MemoryTooLarge);
+            }
+        }
+
+        let addr = addr_wire.state_id();
+
+        let data_wire = wires
+            .get_mut(args.data)
+            .ok_or(AddComponentError::InvalidWireId)?;
+
+        let output_state = output_states.alloc(data_width)?;
+        data_wire.add_driver(output_state);
+
+        Ok(Self {
+            addr_width,
+            data_width,
+            addr,
+            memory: Arc::clone(args.contents),
+            output_state,
+            output_wire: args.data,
+        })
     }
 
     #[cfg(feature = "dot-export")]
     fn node_name(&self) -> Cow<'static, str> {
-        todo!()
+        "ROM".into()
     }
 
     #[cfg(feature = "dot-export")]
     fn output_wires(&self) -> SmallVec<[(WireId, Cow<'static, str>); 1]> {
-        todo!()
+        smallvec![(self.output_wire, "Data".into())]
     }
 
     #[cfg(feature = "dot-export")]
     fn input_wires(&self) -> SmallVec<[(WireStateId, Cow<'static, str>); 2]> {
-        todo!()
+        smallvec![(self.addr, "Addr".into())]
+    }
+
+    fn copy_into(&self, builder: &mut SimulatorBuilder, wire_map: &WireMap) -> AddComponentResult {
+        builder.add_component::<Self>(RomArgs {
+            addr: wire_map.input(self.addr),
+            data: wire_map.output(self.output_wire),
+            contents: &self.memory,
+        })
     }
 
     #[inline]
     fn output_range(&self) -> (OutputStateId, OutputStateId, BitWidth) {
-        (self.output_state, self.output_state, self.bit_width)
+        (self.output_state, self.output_state, self.data_width)
     }
 
     fn update(
         &mut self,
         wire_states: WireStateView,
-        output_states: OutputStateViewMut,
+        mut output_states: OutputStateViewMut,
     ) -> inline_vec!(WireId) {
-        todo!()
+        let [addr, _] = wire_states
+            .get(self.addr, self.addr_width)
+            .expect("invalid wire state ID");
+
+        let [mut output] = output_states
+            .get_mut(self.output_state, self.data_width)
+            .expect("invalid output state ID");
+
+        let result = match to_address(addr).and_then(|addr| self.memory.get(addr)) {
+            Some(entry) => output.copy_from(entry),
+            None => output.copy_from(&InlineLogicState::undefined(self.data_width)),
+        };
+
+        match result {
+            CopyFromResult::Unchanged => smallvec![],
+            CopyFromResult::Changed => smallvec![self.output_wire],
+        }
     }
 }
 
@@ -3036,41 +5461,6 @@ impl LargeComponent for PriorityDecoder {
     }
 }
 
-struct ClockTrigger {
-    prev: Option<bool>,
-    polarity: ClockPolarity,
-}
-
-impl ClockTrigger {
-    #[inline]
-    const fn new(polarity: ClockPolarity) -> Self {
-        Self {
-            prev: None,
-            polarity,
-        }
-    }
-
-    #[inline]
-    fn reset(&mut self) {
-        self.prev = None;
-    }
-
-    #[inline]
-    fn update(&mut self, current: LogicBitState) -> bool {
-        let current = match current {
-            LogicBitState::HighZ | LogicBitState::Undefined => self.prev,
-            LogicBitState::Logic0 => Some(false),
-            LogicBitState::Logic1 => Some(true),
-        };
-
-        let edge = (self.prev == Some(self.polarity.inactive_state()))
-            && (current == Some(self.polarity.active_state()));
-
-        self.prev = current;
-        edge
-    }
-}
-
 pub(crate) struct Register {
     width: NonZeroU8,
     data_in: WireStateId,
@@ -1,5 +1,5 @@
 use crate::logic::*;
-use crate::{CLog2, SafeDivCeil};
+use crate::{CLog2, RotateDirection, SafeDivCeil};
 use itertools::izip;
 use std::num::NonZeroU8;
 use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
@@ -429,6 +429,305 @@ pub(super) fn mul(mut product: LogicStateMut, input_a: LogicStateRef, input_b: L
     }
 }
 
+/// Widens an operand to twice its width, zero or sign extending it
+fn extend_operand(plane: &[u32], bit_width: BitWidth, signed: bool) -> [u32; 2 * MAX_WORD_COUNT] {
+    let word_len = bit_width.word_len() as usize;
+    let last_word_mask = bit_width.last_word_mask();
+
+    let mut extended = [0; 2 * MAX_WORD_COUNT];
+    extended[..word_len].copy_from_slice(plane);
+    extended[word_len - 1] &= last_word_mask;
+
+    let sign_bit = (bit_width.get() - 1) % u32::BITS;
+    if signed && (((extended[word_len - 1] >> sign_bit) & 0x1) != 0) {
+        extended[word_len - 1] |= !last_word_mask;
+        extended[word_len..].fill(u32::MAX);
+    }
+
+    extended
+}
+
+/// Finds the index of the lowest bit that is not 0 or 1
+fn first_invalid_bit(plane_1: &[u32], bit_width: BitWidth) -> u32 {
+    let word_len = plane_1.len();
+
+    for (i, &word) in plane_1.iter().enumerate() {
+        let word = if i == (word_len - 1) {
+            word & bit_width.last_word_mask()
+        } else {
+            word
+        };
+
+        if word != 0 {
+            return (i as u32) * u32::BITS + word.trailing_zeros();
+        }
+    }
+
+    bit_width.get()
+}
+
+/// Writes `output.bit_width()` bits of `product`, starting at bit `offset`, into `output`
+fn write_product_bits(mut output: LogicStateMut, product: &[u32; 2 * MAX_WORD_COUNT], offset: u32) {
+    let word_offset = (offset / u32::BITS) as usize;
+    let bit_offset = offset % u32::BITS;
+
+    let bit_width = output.bit_width();
+    let word_len = bit_width.word_len() as usize;
+
+    let (output_plane_0, output_plane_1) = output.bit_planes_mut();
+    for (i, (output_word_0, output_word_1)) in output_plane_0
+        .iter_mut()
+        .zip(output_plane_1.iter_mut())
+        .enumerate()
+    {
+        let low = product[word_offset + i] >> bit_offset;
+        let high = if bit_offset == 0 {
+            0
+        } else {
+            product.get(word_offset + i + 1).copied().unwrap_or(0) << (u32::BITS - bit_offset)
+        };
+
+        *output_word_0 = low | high;
+        *output_word_1 = 0;
+    }
+
+    output_plane_0[word_len - 1] &= bit_width.last_word_mask();
+}
+
+/// Sets all bits of `output` to undefined
+fn write_undefined(mut output: LogicStateMut) {
+    let (output_plane_0, output_plane_1) = output.bit_planes_mut();
+    output_plane_0.fill(u32::MAX);
+    output_plane_1.fill(u32::MAX);
+}
+
+/// Copies the bits of `input` starting at bit `offset` into `output`
+#[inline]
+pub(super) fn slice(mut output: LogicStateMut, input: LogicStateRef, offset: u32) {
+    assert!((offset + output.bit_width().get()) <= input.bit_width().get());
+    let bit_width = output.bit_width();
+    let word_len = bit_width.word_len() as usize;
+    let word_offset = (offset / u32::BITS) as usize;
+    let bit_offset = offset % u32::BITS;
+
+    let (input_plane_0, input_plane_1) = input.bit_planes();
+    let (output_plane_0, output_plane_1) = output.bit_planes_mut();
+    for (input_plane, output_plane) in [
+        (input_plane_0, output_plane_0),
+        (input_plane_1, output_plane_1),
+    ] {
+        for (i, output_word) in output_plane.iter_mut().enumerate() {
+            let low = input_plane[word_offset + i] >> bit_offset;
+            let high = if bit_offset == 0 {
+                0
+            } else {
+                input_plane.get(word_offset + i + 1).copied().unwrap_or(0)
+                    << (u32::BITS - bit_offset)
+            };
+
+            *output_word = low | high;
+        }
+
+        output_plane[word_len - 1] &= bit_width.last_word_mask();
+    }
+}
+
+/// Multiplies two values into a product of twice their width, split into its low and high half
+#[inline]
+pub(super) fn mul_split(
+    low: LogicStateMut,
+    high: LogicStateMut,
+    input_a: LogicStateRef,
+    input_b: LogicStateRef,
+    signed: bool,
+) {
+    assert_eq!(low.bit_width(), input_a.bit_width());
+    assert_eq!(low.bit_width(), input_b.bit_width());
+    assert_eq!(high.bit_width(), input_a.bit_width());
+    let bit_width = low.bit_width();
+    let wide_word_len = (2 * bit_width.get()).div_ceil(u32::BITS) as usize;
+
+    let (input_a_plane_0, input_a_plane_1) = input_a.bit_planes();
+    let (input_b_plane_0, input_b_plane_1) = input_b.bit_planes();
+    // A single invalid input bit makes the entire product undefined
+    if (first_invalid_bit(input_a_plane_1, bit_width) < bit_width.get())
+        || (first_invalid_bit(input_b_plane_1, bit_width) < bit_width.get())
+    {
+        write_undefined(low);
+        write_undefined(high);
+        return;
+    }
+
+    let input_a = extend_operand(input_a_plane_0, bit_width, signed);
+    let input_b = extend_operand(input_b_plane_0, bit_width, signed);
+
+    let mut product = [0; 2 * MAX_WORD_COUNT];
+    for (i_a, &word_a) in input_a.iter().enumerate().take(wide_word_len) {
+        let mut carry = 0;
+        for (i_b, &word_b) in input_b.iter().enumerate().take(wide_word_len - i_a) {
+            let i = i_a + i_b;
+
+            (product[i], carry) = carrying_mul(word_a, word_b, carry, product[i]);
+        }
+    }
+
+    write_product_bits(low, &product, 0);
+    write_product_bits(high, &product, bit_width.get());
+}
+
+/// Compares two values using a single subtraction, returning whether `a` is less than, equal to and greater than `b`
+#[inline]
+pub(super) fn magnitude_compare(
+    input_a: LogicStateRef,
+    input_b: LogicStateRef,
+    signed: bool,
+) -> [LogicBitState; 3] {
+    assert_eq!(input_a.bit_width(), input_b.bit_width());
+    let bit_width = input_a.bit_width();
+
+    let (input_a_plane_0, input_a_plane_1) = input_a.bit_planes();
+    let (input_b_plane_0, input_b_plane_1) = input_b.bit_planes();
+    if (first_invalid_bit(input_a_plane_1, bit_width) < bit_width.get())
+        || (first_invalid_bit(input_b_plane_1, bit_width) < bit_width.get())
+    {
+        return [LogicBitState::Undefined; 3];
+    }
+
+    // With one extra word the difference cannot overflow, so its sign alone tells which input is smaller
+    let input_a = extend_operand(input_a_plane_0, bit_width, signed);
+    let input_b = extend_operand(input_b_plane_0, bit_width, signed);
+    let word_len = (bit_width.word_len() as usize) + 1;
+
+    let mut borrow = false;
+    let mut zero = true;
+    let mut top_word = 0;
+    for i in 0..word_len {
+        let (diff, borrow_0) = input_a[i].overflowing_sub(input_b[i]);
+        let (diff, borrow_1) = diff.overflowing_sub(borrow as u32);
+        borrow = borrow_0 | borrow_1;
+        zero &= diff == 0;
+        top_word = diff;
+    }
+
+    let less = (top_word >> (u32::BITS - 1)) != 0;
+    [
+        LogicBitState::from_bool(less),
+        LogicBitState::from_bool(zero),
+        LogicBitState::from_bool(!less && !zero),
+    ]
+}
+
+/// Reduces a rotation amount modulo `ring_len`, if all of its bits are valid
+fn ring_offset(amount: LogicStateRef, ring_len: u32) -> Option<u32> {
+    let bit_width = amount.bit_width();
+    let word_len = bit_width.word_len() as usize;
+    let (amount_plane_0, amount_plane_1) = amount.bit_planes();
+
+    let mut offset = 0u64;
+    for i in (0..word_len).rev() {
+        let mask = if i == (word_len - 1) {
+            bit_width.last_word_mask()
+        } else {
+            u32::MAX
+        };
+
+        if (amount_plane_1[i] & mask) != 0 {
+            return None;
+        }
+
+        offset = ((offset << u32::BITS) | ((amount_plane_0[i] & mask) as u64)) % (ring_len as u64);
+    }
+
+    Some(offset as u32)
+}
+
+/// Converts a memory address into an index, if all of its bits are valid
+#[inline]
+pub(super) fn to_address(addr: LogicStateRef) -> Option<usize> {
+    let bit_width = addr.bit_width();
+    let word_len = bit_width.word_len() as usize;
+    let (addr_plane_0, addr_plane_1) = addr.bit_planes();
+
+    let mut index = 0usize;
+    for i in (0..word_len).rev() {
+        let mask = if i == (word_len - 1) {
+            bit_width.last_word_mask()
+        } else {
+            u32::MAX
+        };
+
+        if (addr_plane_1[i] & mask) != 0 {
+            return None;
+        }
+
+        // Addresses that don't fit into a `usize` are out of range for any memory
+        if (index >> (usize::BITS - u32::BITS)) != 0 {
+            return None;
+        }
+
+        index = index.checked_shl(u32::BITS).unwrap_or(0) | ((addr_plane_0[i] & mask) as usize);
+    }
+
+    Some(index)
+}
+
+/// Rotates `input` and `carry_in` by `amount` as a single ring of `width + 1` bits, returning the new carry
+#[inline]
+pub(super) fn rotate_through_carry(
+    mut output: LogicStateMut,
+    input: LogicStateRef,
+    carry_in: LogicBitState,
+    amount: LogicStateRef,
+    direction: RotateDirection,
+) -> LogicBitState {
+    assert_eq!(output.bit_width(), input.bit_width());
+    let width = output.bit_width().get();
+    let ring_len = width + 1;
+
+    // The carry occupies the ring position directly above the most significant bit
+    let ring_bit = |index: u32| {
+        let bit = if index == width {
+            carry_in
+        } else {
+            input.bit(index).expect("invalid bit index")
+        };
+
+        match bit {
+            LogicBitState::HighZ => LogicBitState::Undefined,
+            bit => bit,
+        }
+    };
+
+    // If the amount is not fully known, neither is the position of any bit
+    let offset = ring_offset(amount, ring_len);
+    let rotated_bit = |index: u32| match (offset, direction) {
+        (None, _) => LogicBitState::Undefined,
+        (Some(offset), RotateDirection::Left) => ring_bit((index + ring_len - offset) % ring_len),
+        (Some(offset), RotateDirection::Right) => ring_bit((index + offset) % ring_len),
+    };
+
+    let (output_plane_0, output_plane_1) = output.bit_planes_mut();
+    for (i, (word_0, word_1)) in izip!(output_plane_0, output_plane_1).enumerate() {
+        *word_0 = 0;
+        *word_1 = 0;
+
+        let word_start = (i as u32) * u32::BITS;
+        for bit in 0..u32::BITS.min(width - word_start) {
+            let (bit_0, bit_1) = match rotated_bit(word_start + bit) {
+                LogicBitState::Logic0 => (0, 0),
+                LogicBitState::Logic1 => (1, 0),
+                LogicBitState::HighZ => (0, 1),
+                LogicBitState::Undefined => (1, 1),
+            };
+
+            *word_0 |= bit_0 << bit;
+            *word_1 |= bit_1 << bit;
+        }
+    }
+
+    rotated_bit(width)
+}
+
 /*
 
 
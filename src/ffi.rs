@@ -125,13 +125,16 @@ impl From<crate::import::yosys::YosysModuleImportError> for FfiError {
 impl From<AddComponentError> for FfiError {
     fn from(value: AddComponentError) -> Self {
         match value {
-            AddComponentError::TooManyComponents => Self::ResourceLimitReached,
+            AddComponentError::TooManyComponents
+            | AddComponentError::MemoryTooLarge
+            | AddComponentError::ComponentLimitReached { .. } => Self::ResourceLimitReached,
             AddComponentError::InvalidWireId => Self::InvalidWireId,
             AddComponentError::WireWidthMismatch => Self::WireWidthMismatch,
             AddComponentError::WireWidthIncompatible => Self::WireWidthIncompatible,
             AddComponentError::OffsetOutOfRange => Self::OffsetOutOfRange,
             AddComponentError::TooFewInputs => Self::TooFewInputs,
             AddComponentError::InvalidInputCount => Self::InvalidInputCount,
+            AddComponentError::ConflictingDrives => Self::Conflict,
         }
     }
 }
@@ -143,6 +146,16 @@ impl From<InvalidWireIdError> for FfiError {
     }
 }
 
+impl From<SetWireDriveError> for FfiError {
+    #[inline]
+    fn from(value: SetWireDriveError) -> Self {
+        match value {
+            SetWireDriveError::InvalidWireId => Self::InvalidWireId,
+            SetWireDriveError::InvalidBitWidth { .. } => Self::WireWidthMismatch,
+        }
+    }
+}
+
 impl From<InvalidComponentIdError> for FfiError {
     #[inline]
     fn from(_: InvalidComponentIdError) -> Self {
@@ -181,6 +181,22 @@ impl<T: Id> IdVec<T> {
         }
     }
 
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+        if self.len() <= INLINE_CAP {
+            unsafe {
+                // SAFETY: when len <= INLINE_CAP, the active variant is `inline`
+                let len = self.inline.len as usize;
+                let ptr: *mut T = self.inline.data.as_mut_slice().as_mut_ptr().cast();
+                std::slice::from_raw_parts_mut(ptr, len)
+            }
+        } else {
+            unsafe {
+                // SAFETY: when len > INLINE_CAP, the active variant is `heap`
+                std::slice::from_raw_parts_mut(self.heap.data.as_ptr(), self.heap.len as usize)
+            }
+        }
+    }
+
     #[inline]
     pub(crate) fn contains(&self, id: T) -> bool {
         self.as_slice().contains(&id)
@@ -192,6 +208,16 @@ impl<T: Id> IdVec<T> {
     }
 }
 
+impl<T: Id> Clone for IdVec<T> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::new();
+        for id in self.iter() {
+            clone.push(id);
+        }
+        clone
+    }
+}
+
 impl<T: Id> Drop for IdVec<T> {
     fn drop(&mut self) {
         if self.len() > INLINE_CAP {
@@ -211,6 +237,23 @@ macro_rules! def_id_list {
         #[repr(transparent)]
         pub(crate) struct $list_name(Vec<sync_unsafe_cell::SyncUnsafeCell<$t>>);
 
+        impl Clone for $list_name {
+            fn clone(&self) -> Self {
+                Self(
+                    self.0
+                        .iter()
+                        .map(|t| {
+                            let t = unsafe {
+                                // SAFETY: since we have a shared reference to `self`, no mutable references exist
+                                &*t.get()
+                            };
+                            sync_unsafe_cell::SyncUnsafeCell::new(t.clone())
+                        })
+                        .collect(),
+                )
+            }
+        }
+
         #[allow(dead_code)]
         impl $list_name {
             #[inline]
@@ -223,6 +266,11 @@ macro_rules! def_id_list {
                 crate::AllocationSize(self.0.capacity() * std::mem::size_of::<$t>())
             }
 
+            #[inline]
+            pub(crate) fn reserve_exact(&mut self, additional: usize) {
+                self.0.reserve_exact(additional);
+            }
+
             #[inline]
             pub(crate) fn push(&mut self, item: $t) -> Option<$id_name> {
                 let current_len = u32::try_from(self.0.len()).ok()?;
@@ -5,11 +5,15 @@
 //#[cfg(feature = "yosys-import")]
 //pub mod yosys;
 
-use crate::{HashMap, WireId};
+use crate::id::Id;
+use crate::{
+    BitWidth, ComponentId, HashMap, IntoLogicStateRef, InvalidWireIdError, LogicState,
+    SetWireDriveError, SimulationRunResult, Simulator, WireId,
+};
 use std::sync::Arc;
 
 /// The list of connections of an imported module
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct ModuleConnections {
     /// The inputs of the module
     pub inputs: HashMap<Arc<str>, WireId>,
@@ -31,3 +35,195 @@ pub trait ModuleImporter {
         builder: &mut crate::SimulatorBuilder,
     ) -> Result<ModuleConnections, Self::Error>;
 }
+
+/// Errors that can occur when accessing the ports of a [TestFixture]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum TestFixtureError {
+    /// No port with the given name exists
+    UnknownPort(Arc<str>),
+    /// The value did not have the width of the port
+    WidthMismatch {
+        /// The width of the port
+        expected: BitWidth,
+        /// The width of the value
+        actual: BitWidth,
+    },
+    /// The value did not fit into the width of the port
+    ValueTooWide,
+    /// The wire of the port was not part of the simulation
+    InvalidWireId,
+    /// The input port is also driven by a component inside the module
+    DrivenInternally {
+        /// The name of the port
+        port: Arc<str>,
+        /// A component driving the port
+        component: ComponentId,
+    },
+}
+
+impl From<InvalidWireIdError> for TestFixtureError {
+    #[inline]
+    fn from(_: InvalidWireIdError) -> Self {
+        TestFixtureError::InvalidWireId
+    }
+}
+
+impl std::fmt::Display for TestFixtureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownPort(name) => write!(f, "the port '{name}' does not exist"),
+            Self::WidthMismatch { expected, actual } => write!(
+                f,
+                "the value did not have the width of the port: expected width {expected}, got {actual}"
+            ),
+            Self::ValueTooWide => f.write_str("the value did not fit into the width of the port"),
+            Self::InvalidWireId => f.write_str("the wire of the port was not part of the simulation"),
+            Self::DrivenInternally { port, component } => write!(
+                f,
+                "the input port '{port}' is driven by component {} and cannot also be driven externally",
+                component.to_bits()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TestFixtureError {}
+
+/// Drives and reads the ports of a module by name, e.g. to apply test vectors to an imported design
+///
+/// Example:
+/// ```
+/// use gsim::import::TestFixture;
+/// use gsim::*;
+///
+/// let mut builder = SimulatorBuilder::default();
+/// let a = builder.add_wire(bit_width!(8)).unwrap();
+/// let b = builder.add_wire(bit_width!(8)).unwrap();
+/// let sum = builder.add_wire(bit_width!(8)).unwrap();
+/// builder.add_add(a, b, sum).unwrap();
+/// builder.mark_input(a, "a").unwrap();
+/// builder.mark_input(b, "b").unwrap();
+/// builder.mark_output(sum, "sum").unwrap();
+///
+/// let sim = builder.build();
+/// let ports = sim.ports().clone();
+/// let mut fixture = TestFixture::new(sim, &ports);
+///
+/// fixture.set("a", 3).unwrap();
+/// fixture.set("b", 4).unwrap();
+/// fixture.run().unwrap();
+/// assert_eq!(fixture.get("sum").unwrap(), LogicState::from_u32(7, bit_width!(8)));
+/// ```
+pub struct TestFixture {
+    sim: Simulator,
+    ports: ModuleConnections,
+    max_steps: u64,
+}
+
+impl TestFixture {
+    const DEFAULT_MAX_STEPS: u64 = 10000;
+
+    /// Creates a fixture driving the ports of `ports` in `sim`
+    pub fn new(sim: Simulator, ports: &ModuleConnections) -> Self {
+        Self {
+            sim,
+            ports: ports.clone(),
+            max_steps: Self::DEFAULT_MAX_STEPS,
+        }
+    }
+
+    /// Sets the maximum number of steps [TestFixture::run] may take to settle
+    #[inline]
+    pub fn with_max_steps(mut self, max_steps: u64) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// The simulator driven by this fixture
+    #[inline]
+    pub fn sim(&self) -> &Simulator {
+        &self.sim
+    }
+
+    /// The simulator driven by this fixture
+    #[inline]
+    pub fn sim_mut(&mut self) -> &mut Simulator {
+        &mut self.sim
+    }
+
+    /// Turns the fixture back into its simulator
+    #[inline]
+    pub fn into_sim(self) -> Simulator {
+        self.sim
+    }
+
+    fn port_width(&self, wire: WireId) -> Result<BitWidth, TestFixtureError> {
+        let [state, _] = self.sim.get_wire_state_and_drive(wire)?;
+        Ok(state.bit_width())
+    }
+
+    /// Drives the input port `name` to the number `value`
+    ///
+    /// The value is sized to the width of the port and must fit into it.
+    pub fn set(&mut self, name: &str, value: u64) -> Result<(), TestFixtureError> {
+        let wire = self.input(name)?;
+        let bit_width = self.port_width(wire)?;
+        if (bit_width.get() < u64::BITS) && ((value >> bit_width.get()) != 0) {
+            return Err(TestFixtureError::ValueTooWide);
+        }
+
+        let state = LogicState::from_big_int(bit_width, &[value as u32, (value >> 32) as u32]);
+        self.set_state(name, &state)
+    }
+
+    /// Drives the input port `name` to `state`, which must have the width of the port
+    pub fn set_state<'a>(
+        &mut self,
+        name: &str,
+        state: impl IntoLogicStateRef<'a>,
+    ) -> Result<(), TestFixtureError> {
+        let wire = self.input(name)?;
+        match self.sim.set_wire_drive(wire, state) {
+            Ok(()) => Ok(()),
+            Err(SetWireDriveError::InvalidBitWidth { expected, actual }) => {
+                Err(TestFixtureError::WidthMismatch { expected, actual })
+            }
+            Err(SetWireDriveError::InvalidWireId) => Err(TestFixtureError::InvalidWireId),
+            Err(SetWireDriveError::DrivenInternally { component, .. }) => {
+                Err(TestFixtureError::DrivenInternally {
+                    port: name.into(),
+                    component,
+                })
+            }
+        }
+    }
+
+    /// Reads the port `name`
+    ///
+    /// Both outputs and inputs can be read, outputs are looked up first. Outputs return their current state,
+    /// inputs the value they are driven to, which is visible before [TestFixture::run] was called.
+    pub fn get(&self, name: &str) -> Result<LogicState, TestFixtureError> {
+        if let Some(&wire) = self.ports.outputs.get(name) {
+            let [state, _] = self.sim.get_wire_state_and_drive(wire)?;
+            Ok(state.to_owned())
+        } else {
+            let [_, drive] = self.sim.get_wire_state_and_drive(self.input(name)?)?;
+            Ok(drive.to_owned())
+        }
+    }
+
+    /// Runs the simulation until it settles, see [Simulator::run_sim]
+    #[inline]
+    pub fn run(&mut self) -> SimulationRunResult {
+        self.sim.run_sim(self.max_steps)
+    }
+
+    fn input(&self, name: &str) -> Result<WireId, TestFixtureError> {
+        self.ports
+            .inputs
+            .get(name)
+            .copied()
+            .ok_or_else(|| TestFixtureError::UnknownPort(name.into()))
+    }
+}
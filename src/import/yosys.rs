@@ -951,6 +951,7 @@ impl ModuleImporter for YosysModuleImporter {
     ) -> Result<ModuleConnections, Self::Error> {
         let max_net_id = self.module.max_net_id();
         let mut wire_map = WireMap::new(max_net_id);
+        builder.reserve(max_net_id, self.module.cells.len());
 
         let mut connections = ModuleConnections::default();
         for (port_name, port) in &self.module.ports {
@@ -63,11 +63,13 @@ mod test;
 use component::*;
 use id::*;
 use smallvec::SmallVec;
-use std::num::NonZeroU8;
+use std::collections::BTreeMap;
+use std::num::{NonZeroU64, NonZeroU8, NonZeroUsize};
+use std::ops::ControlFlow;
 use std::sync::{Arc, Mutex};
 use wire::*;
 
-pub use component::ComponentId;
+pub use component::{ComponentId, ComponentKind};
 pub use logic::*;
 pub use wire::WireId;
 
@@ -106,6 +108,14 @@ macro_rules! inline_vec {
 
 use inline_vec;
 
+/// Emits a diagnostic about the progress of the simulation if the `log` feature is enabled
+macro_rules! sim_log {
+    ($level:ident, $($arg:tt)*) => {
+        #[cfg(feature = "log")]
+        log::$level!(target: "gsim::sim", $($arg)*);
+    };
+}
+
 trait SafeDivCeil<Rhs = Self> {
     type Output;
 
@@ -228,6 +238,49 @@ pub struct SimulationStats {
     pub output_state_alloc_size: AllocationSize,
 }
 
+/// A wire of a simulation together with its name and width
+#[derive(Debug, Clone, Copy)]
+pub struct WireInfo<'a> {
+    /// The ID of the wire
+    pub id: WireId,
+    /// The name of the wire, if one has been assigned
+    pub name: Option<&'a str>,
+    /// The width of the wire
+    pub bit_width: BitWidth,
+}
+
+/// A component of a simulation together with its kind and name
+#[derive(Debug, Clone, Copy)]
+pub struct ComponentInfo<'a> {
+    /// The ID of the component
+    pub id: ComponentId,
+    /// The kind of the component
+    pub kind: ComponentKind,
+    /// The name of the component, if one has been assigned
+    pub name: Option<&'a str>,
+}
+
+/// Maps the wires and components of a circuit merged using [SimulatorBuilder::extend_from] to their new IDs
+#[derive(Debug, Default, Clone)]
+pub struct IdRemap {
+    wires: HashMap<WireId, WireId>,
+    components: HashMap<ComponentId, ComponentId>,
+}
+
+impl IdRemap {
+    /// The new ID of a wire of the merged circuit
+    #[inline]
+    pub fn wire(&self, wire: WireId) -> Option<WireId> {
+        self.wires.get(&wire).copied()
+    }
+
+    /// The new ID of a component of the merged circuit
+    #[inline]
+    pub fn component(&self, component: ComponentId) -> Option<ComponentId> {
+        self.components.get(&component).copied()
+    }
+}
+
 /// Contains data of all errors that occurred in a simulation
 #[derive(Debug, Clone)]
 pub struct SimulationErrors {
@@ -247,6 +300,17 @@ enum SimulationStepResult {
     Err(SimulationErrors),
 }
 
+/// The progress of a running simulation, see [Simulator::run_sim_with_callback]
+#[derive(Debug, Clone, Copy)]
+pub struct SimulationProgress {
+    /// The number of steps performed so far
+    pub steps: u64,
+    /// The number of wires queued for the next step
+    pub queued_wires: usize,
+    /// The number of components queued for the next step
+    pub queued_components: usize,
+}
+
 /// The result of running a simulation
 #[derive(Debug, Clone)]
 #[must_use]
@@ -294,6 +358,17 @@ pub enum AddComponentError {
     TooFewInputs,
     /// The number of inputs was not valid for the component
     InvalidInputCount,
+    /// A memory component would have more entries than allowed
+    MemoryTooLarge,
+    /// Both wires that were tied together already had a drive set
+    ConflictingDrives,
+    /// The maximum number of components of one kind was reached
+    ComponentLimitReached {
+        /// The kind of component that could not be added
+        kind: ComponentKind,
+        /// The number of components of this kind already in the simulation
+        count: usize,
+    },
 }
 
 impl From<OutOfMemoryError> for AddComponentError {
@@ -318,213 +393,1563 @@ impl From<InvalidWireIdError> for AddComponentError {
 #[derive(Debug, Clone)]
 pub struct InvalidComponentIdError;
 
-/// The result of adding a component to a simulator
-pub type AddComponentResult = Result<ComponentId, AddComponentError>;
+/// Errors that can occur when setting the drive of a wire
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum SetWireDriveError {
+    /// The specified wire ID was not part of the simulation
+    InvalidWireId,
+    /// The new drive did not have the same width as the wire
+    InvalidBitWidth {
+        /// The width of the wire
+        expected: BitWidth,
+        /// The width of the new drive
+        actual: BitWidth,
+    },
+    /// The wire is driven by a component, so its drive can only be released to high impedance
+    DrivenInternally {
+        /// The wire that was to be driven
+        wire: WireId,
+        /// A component driving the wire
+        component: ComponentId,
+    },
+}
 
-struct SimulatorData {
-    wires: WireList,
-    wire_states: WireStateAllocator,
+impl From<InvalidWireIdError> for SetWireDriveError {
+    #[inline]
+    fn from(_: InvalidWireIdError) -> Self {
+        SetWireDriveError::InvalidWireId
+    }
+}
 
-    components: ComponentStorage,
-    output_states: OutputStateAllocator,
+/// Errors that can occur when defining a clock domain
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum DefineClockDomainError {
+    /// The specified clock wire was not part of the simulation
+    InvalidWireId,
+    /// The clock wire was not 1 bit wide
+    WireWidthIncompatible,
+    /// A clock domain with the same name was already defined
+    DuplicateName,
+}
 
-    wire_update_queue: Vec<WireId>,
-    component_update_queue: Vec<ComponentId>,
+impl From<InvalidWireIdError> for DefineClockDomainError {
+    #[inline]
+    fn from(_: InvalidWireIdError) -> Self {
+        DefineClockDomainError::InvalidWireId
+    }
+}
 
-    wire_names: HashMap<WireId, Arc<str>>,
-    component_names: HashMap<ComponentId, Arc<str>>,
+/// A specified clock domain was not defined in the simulation
+#[derive(Debug, Clone)]
+pub struct UnknownClockDomainError;
+
+/// The circuit contains combinational cycles and cannot be evaluated in a single pass
+pub struct HasCyclesError {
+    builder: Box<SimulatorBuilder>,
 }
 
-impl SimulatorData {
+impl HasCyclesError {
+    /// Returns the builder that failed to build, so a regular simulator can be created instead
     #[inline]
-    fn new() -> Self {
-        Self {
-            wires: WireList::new(),
-            wire_states: WireStateAllocator::new(),
+    pub fn into_builder(self) -> SimulatorBuilder {
+        *self.builder
+    }
+}
 
-            components: ComponentStorage::default(),
-            output_states: OutputStateAllocator::new(),
+/// The circuit could not be evaluated in a single combinational pass
+#[derive(Debug, Clone)]
+pub enum NotCombinationalError {
+    /// The circuit contains combinational cycles
+    HasCycles,
+    /// Updating the components again after the pass still changed the state of wires
+    Unsettled,
+    /// Wires had multiple conflicting drivers
+    Conflict(SimulationErrors),
+}
 
-            wire_update_queue: Vec::new(),
-            component_update_queue: Vec::new(),
+/// Two simulators that were expected to share the same topology did not
+#[derive(Debug, Clone)]
+pub struct TopologyMismatchError;
 
-            wire_names: HashMap::new(),
-            component_names: HashMap::new(),
-        }
+/// Errors that can occur when accessing a register through its output wire
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum RegisterAccessError {
+    /// The specified wire ID was not part of the simulation
+    InvalidWireId,
+    /// The wire was not driven by a register
+    NoRegister,
+    /// The value did not have the same width as the register
+    WireWidthMismatch,
+}
+
+impl From<InvalidWireIdError> for RegisterAccessError {
+    #[inline]
+    fn from(_: InvalidWireIdError) -> Self {
+        RegisterAccessError::InvalidWireId
     }
+}
+
+/// Errors that can occur when reading a single bit of a wire
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum GetWireBitError {
+    /// The specified wire ID was not part of the simulation
+    InvalidWireId,
+    /// The bit index was not smaller than the width of the wire
+    IndexOutOfRange,
+}
 
+impl From<InvalidWireIdError> for GetWireBitError {
     #[inline]
-    fn iter_wire_ids(&self) -> impl Iterator<Item = WireId> + '_ {
-        self.wires.ids()
+    fn from(_: InvalidWireIdError) -> Self {
+        GetWireBitError::InvalidWireId
     }
+}
+
+/// Errors that can occur when loading a state image
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum StateImageError {
+    /// Reading the image failed
+    Io(std::io::Error),
+    /// The data was truncated or not a state image
+    InvalidFormat,
+    /// The image was written by an unsupported version of the format
+    UnsupportedVersion(u32),
+    /// The memory components in the image did not match the ones in the simulation
+    LayoutMismatch,
+}
 
+impl From<std::io::Error> for StateImageError {
     #[inline]
-    fn iter_component_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
-        self.components.ids()
+    fn from(err: std::io::Error) -> Self {
+        match err.kind() {
+            std::io::ErrorKind::UnexpectedEof => StateImageError::InvalidFormat,
+            _ => StateImageError::Io(err),
+        }
     }
+}
 
-    fn set_wire_drive<'a>(
-        &mut self,
-        wire: WireId,
-        new_drive: impl IntoLogicStateRef<'a>,
-    ) -> Result<(), InvalidWireIdError> {
-        let wire = self.wires.get(wire).ok_or(InvalidWireIdError)?;
-        let [_, mut drive] = self
-            .wire_states
-            .get_mut(wire.state_id(), wire.bit_width())
-            .expect("invalid wire state ID");
-        // TODO: properly report width mismatch error
+impl std::fmt::Debug for HasCyclesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HasCyclesError")
+    }
+}
 
-        let new_drive = new_drive.into_logic_state_ref();
-        let (src_plane_0, src_plane_1) = new_drive.bit_planes();
-        let (dst_plane_0, dst_plane_1) = drive.bit_planes_mut();
-        dst_plane_0.copy_from_slice(src_plane_0);
-        dst_plane_1.copy_from_slice(src_plane_1);
+impl std::fmt::Display for AddComponentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Self::TooManyComponents => "the memory limit for components was reached",
+            Self::InvalidWireId => "a specified wire ID was not part of the simulation",
+            Self::WireWidthMismatch => "wires that were expected to did not have the same width",
+            Self::WireWidthIncompatible => "a wire had a width incompatible with the component",
+            Self::OffsetOutOfRange => "an offset was outside the range of its wire's width",
+            Self::TooFewInputs => "too few inputs were specified",
+            Self::InvalidInputCount => "the number of inputs was not valid for the component",
+            Self::MemoryTooLarge => "the memory component would have more entries than allowed",
+            Self::ConflictingDrives => "both tied wires already had a drive set",
+            Self::ComponentLimitReached { kind, count } => {
+                return write!(
+                    f,
+                    "the simulation already contains the maximum of {count} components of kind {kind:?}"
+                );
+            }
+        };
 
-        Ok(())
+        f.write_str(msg)
     }
+}
 
-    fn get_wire_state_and_drive(
-        &self,
-        wire: WireId,
-    ) -> Result<[LogicStateRef; 2], InvalidWireIdError> {
-        let wire = self.wires.get(wire).ok_or(InvalidWireIdError)?;
-        Ok(self
-            .wire_states
-            .get(wire.state_id(), wire.bit_width())
-            .expect("invalid wire state ID"))
+impl std::error::Error for AddComponentError {}
+
+impl std::fmt::Display for InvalidWireIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the specified wire ID was not part of the simulation")
     }
+}
 
-    //fn get_component_data(
-    //    &self,
-    //    component: ComponentId,
-    //) -> Result<ComponentData<'_, Immutable>, InvalidComponentIdError> {
-    //    self.components
-    //        .get(component)
-    //        .map(Component::get_data)
-    //        .ok_or(InvalidComponentIdError)
-    //}
+impl std::error::Error for InvalidWireIdError {}
 
-    //fn get_component_data_mut(
-    //    &mut self,
-    //    component: ComponentId,
-    //) -> Result<ComponentData<'_, Mutable>, InvalidComponentIdError> {
-    //    self.components
-    //        .get_mut(component)
-    //        .map(Component::get_data_mut)
-    //        .ok_or(InvalidComponentIdError)
-    //}
+impl std::fmt::Display for InvalidComponentIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the specified component ID was not part of the simulation")
+    }
+}
 
-    fn set_wire_name<S: Into<Arc<str>>>(
-        &mut self,
-        wire: WireId,
-        name: S,
-    ) -> Result<(), InvalidWireIdError> {
-        if self.wires.get(wire).is_none() {
-            return Err(InvalidWireIdError);
+impl std::error::Error for InvalidComponentIdError {}
+
+impl std::fmt::Display for SetWireDriveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidWireId => f.write_str("the specified wire ID was not part of the simulation"),
+            Self::InvalidBitWidth { expected, actual } => write!(
+                f,
+                "the drive did not have the width of the wire: expected width {expected}, got {actual}"
+            ),
+            Self::DrivenInternally { wire, component } => write!(
+                f,
+                "wire {} is driven by component {} and cannot also be driven externally",
+                wire.to_bits(),
+                component.to_bits()
+            ),
         }
+    }
+}
 
-        self.wire_names.insert(wire, name.into());
-        Ok(())
+impl std::error::Error for SetWireDriveError {}
+
+impl std::fmt::Display for DefineClockDomainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Self::InvalidWireId => "the specified clock wire was not part of the simulation",
+            Self::WireWidthIncompatible => "the clock wire was not 1 bit wide",
+            Self::DuplicateName => "a clock domain with the same name was already defined",
+        };
+
+        f.write_str(msg)
     }
+}
 
-    fn get_wire_name(&self, wire: WireId) -> Result<Option<&str>, InvalidWireIdError> {
-        if self.wires.get(wire).is_none() {
-            return Err(InvalidWireIdError);
-        }
+impl std::error::Error for DefineClockDomainError {}
 
-        Ok(self.wire_names.get(&wire).map(|name| &**name))
+impl std::fmt::Display for UnknownClockDomainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the specified clock domain was not defined in the simulation")
     }
+}
 
-    fn set_component_name<S: Into<Arc<str>>>(
-        &mut self,
-        component: ComponentId,
-        name: S,
-    ) -> Result<(), InvalidComponentIdError> {
-        if self.components.component_exists(component) {
-            return Err(InvalidComponentIdError);
-        }
+impl std::error::Error for UnknownClockDomainError {}
 
-        self.component_names.insert(component, name.into());
-        Ok(())
+impl std::fmt::Display for HasCyclesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the circuit contains combinational cycles")
     }
+}
 
-    fn get_component_name(
-        &self,
-        component: ComponentId,
-    ) -> Result<Option<&str>, InvalidComponentIdError> {
-        if self.components.component_exists(component) {
-            return Err(InvalidComponentIdError);
-        }
+impl std::error::Error for HasCyclesError {}
 
-        Ok(self.component_names.get(&component).map(|name| &**name))
+impl std::fmt::Display for NotCombinationalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Self::HasCycles => "the circuit contains combinational cycles",
+            Self::Unsettled => "the circuit did not settle in a single pass",
+            Self::Conflict(_) => "wires had multiple conflicting drivers",
+        };
+
+        f.write_str(msg)
     }
+}
 
-    fn stats(&self) -> SimulationStats {
-        todo!()
-        //    let (small_component_count, large_component_count) = self.components.component_counts();
+impl std::error::Error for NotCombinationalError {}
 
-        //    SimulationStats {
-        //        wire_count: self.wires.wire_count(),
-        //        wire_alloc_size: self.wires.alloc_size(),
-        //        wire_width_alloc_size: self.wire_states.width_alloc_size(),
-        //        wire_drive_alloc_size: self.wire_states.drive_alloc_size(),
-        //        wire_state_alloc_size: self.wire_states.state_alloc_size(),
-        //        small_component_count,
-        //        large_component_count,
-        //        component_alloc_size: self.components.alloc_size(),
-        //        large_component_alloc_size: self.components.large_alloc_size(),
-        //        output_width_alloc_size: self.output_states.width_alloc_size(),
-        //        output_state_alloc_size: self.output_states.state_alloc_size(),
-        //    }
+impl std::fmt::Display for TopologyMismatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the simulators were not built from the same netlist")
     }
+}
 
-    #[cfg(feature = "dot-export")]
-    fn write_dot<W: std::io::Write>(
-        &self,
-        mut writer: W,
-        show_states: bool,
-    ) -> std::io::Result<()> {
-        todo!()
-        //    writeln!(writer, "digraph {{")?;
+impl std::error::Error for TopologyMismatchError {}
 
-        //    let mut wire_state_map = HashMap::new();
-        //    for wire_id in self.wires.ids() {
-        //        let wire = &self.wires.get(wire_id).unwrap();
-        //        let width = self.wire_states.get_width(wire.state);
-        //        wire_state_map.insert(wire.state, wire_id);
+impl std::fmt::Display for RegisterAccessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Self::InvalidWireId => "the specified wire ID was not part of the simulation",
+            Self::NoRegister => "the wire was not driven by a register",
+            Self::WireWidthMismatch => "the value did not have the same width as the register",
+        };
 
-        //        #[allow(clippy::collapsible_else_if)]
-        //        if show_states {
-        //            if let Some(name) = self.wire_names.get(&wire_id) {
-        //                let state = self.get_wire_state(wire_id).unwrap().display_string(width);
-        //                if &**name == state.as_str() {
-        //                    // Don't print constant wire states twice
-        //                    writeln!(
-        //                        writer,
-        //                        "    W{}[label=\"{}\" shape=\"diamond\"];",
-        //                        wire_id.to_bits(),
-        //                        name,
-        //                    )?;
-        //                } else {
-        //                    writeln!(
-        //                        writer,
-        //                        "    W{}[label=\"{} ({})\" shape=\"diamond\"];",
-        //                        wire_id.to_bits(),
-        //                        name,
-        //                        state,
-        //                    )?;
-        //                }
-        //            } else {
-        //                writeln!(
-        //                    writer,
-        //                    "    W{}[label=\"{}\" shape=\"diamond\"];",
-        //                    wire_id.to_bits(),
-        //                    self.get_wire_state(wire_id).unwrap().display_string(width),
-        //                )?;
-        //            }
-        //        } else {
-        //            if let Some(name) = self.wire_names.get(&wire_id) {
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for RegisterAccessError {}
+
+impl std::fmt::Display for GetWireBitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Self::InvalidWireId => "the specified wire ID was not part of the simulation",
+            Self::IndexOutOfRange => "the bit index was outside the width of the wire",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for GetWireBitError {}
+
+impl std::fmt::Display for StateImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Self::Io(err) => return write!(f, "failed to read the image: {err}"),
+            Self::InvalidFormat => "the data was truncated or not a state image",
+            Self::UnsupportedVersion(version) => {
+                return write!(f, "the image format version {version} is not supported")
+            }
+            Self::LayoutMismatch => {
+                "the memory components in the image did not match the simulation"
+            }
+        };
+
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for StateImageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Any error that can be returned by this crate
+///
+/// The specific error types convert into this one, so `?` can be used uniformly
+/// in code that mixes building and simulating circuits.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GsimError {
+    /// A component could not be added
+    AddComponent(AddComponentError),
+    /// A specified wire ID was not part of the simulation
+    InvalidWireId(InvalidWireIdError),
+    /// A specified component ID was not part of the simulation
+    InvalidComponentId(InvalidComponentIdError),
+    /// The drive of a wire could not be set
+    SetWireDrive(SetWireDriveError),
+    /// A clock domain could not be defined
+    DefineClockDomain(DefineClockDomainError),
+    /// A specified clock domain was not defined in the simulation
+    UnknownClockDomain(UnknownClockDomainError),
+    /// The circuit could not be built for single-pass evaluation
+    HasCycles(HasCyclesError),
+    /// The circuit could not be evaluated in a single combinational pass
+    NotCombinational(NotCombinationalError),
+    /// Two simulators did not share the same topology
+    TopologyMismatch(TopologyMismatchError),
+    /// A register could not be accessed through its output wire
+    RegisterAccess(RegisterAccessError),
+    /// A single bit of a wire could not be read
+    GetWireBit(GetWireBitError),
+    /// A state image could not be loaded
+    StateImage(StateImageError),
+    /// A port of a test fixture could not be accessed
+    TestFixture(import::TestFixtureError),
+    /// A logic state could not be parsed
+    LogicStateFromStr(LogicStateFromStrError),
+}
+
+impl std::fmt::Display for GsimError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AddComponent(err) => write!(f, "failed to add component: {err}"),
+            Self::InvalidWireId(err) => std::fmt::Display::fmt(err, f),
+            Self::InvalidComponentId(err) => std::fmt::Display::fmt(err, f),
+            Self::SetWireDrive(err) => write!(f, "failed to set wire drive: {err}"),
+            Self::DefineClockDomain(err) => write!(f, "failed to define clock domain: {err}"),
+            Self::UnknownClockDomain(err) => std::fmt::Display::fmt(err, f),
+            Self::HasCycles(err) => std::fmt::Display::fmt(err, f),
+            Self::NotCombinational(err) => std::fmt::Display::fmt(err, f),
+            Self::TopologyMismatch(err) => std::fmt::Display::fmt(err, f),
+            Self::RegisterAccess(err) => write!(f, "failed to access register: {err}"),
+            Self::GetWireBit(err) => write!(f, "failed to read wire bit: {err}"),
+            Self::StateImage(err) => write!(f, "failed to load state image: {err}"),
+            Self::TestFixture(err) => write!(f, "failed to access test fixture port: {err}"),
+            Self::LogicStateFromStr(err) => write!(f, "failed to parse logic state: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for GsimError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::AddComponent(err) => Some(err),
+            Self::InvalidWireId(err) => Some(err),
+            Self::InvalidComponentId(err) => Some(err),
+            Self::SetWireDrive(err) => Some(err),
+            Self::DefineClockDomain(err) => Some(err),
+            Self::UnknownClockDomain(err) => Some(err),
+            Self::HasCycles(err) => Some(err),
+            Self::NotCombinational(err) => Some(err),
+            Self::TopologyMismatch(err) => Some(err),
+            Self::RegisterAccess(err) => Some(err),
+            Self::GetWireBit(err) => Some(err),
+            Self::StateImage(err) => Some(err),
+            Self::TestFixture(err) => Some(err),
+            Self::LogicStateFromStr(err) => Some(err),
+        }
+    }
+}
+
+macro_rules! gsim_error_from {
+    ($($variant:ident($err:ty)),* $(,)?) => {
+        $(
+            impl From<$err> for GsimError {
+                #[inline]
+                fn from(err: $err) -> Self {
+                    Self::$variant(err)
+                }
+            }
+        )*
+    };
+}
+
+gsim_error_from!(
+    AddComponent(AddComponentError),
+    InvalidWireId(InvalidWireIdError),
+    InvalidComponentId(InvalidComponentIdError),
+    SetWireDrive(SetWireDriveError),
+    DefineClockDomain(DefineClockDomainError),
+    UnknownClockDomain(UnknownClockDomainError),
+    HasCycles(HasCyclesError),
+    NotCombinational(NotCombinationalError),
+    TopologyMismatch(TopologyMismatchError),
+    RegisterAccess(RegisterAccessError),
+    GetWireBit(GetWireBitError),
+    StateImage(StateImageError),
+    TestFixture(import::TestFixtureError),
+    LogicStateFromStr(LogicStateFromStrError),
+);
+
+/// The result of adding a component to a simulator
+pub type AddComponentResult = Result<ComponentId, AddComponentError>;
+
+#[derive(Debug, Clone, Copy)]
+struct ClockDomain {
+    clock: WireId,
+    polarity: ClockPolarity,
+}
+
+/// Translates wires of one circuit into wires of another
+#[derive(Default)]
+pub(crate) struct WireMap {
+    state_wires: HashMap<WireStateId, WireId>,
+    wires: HashMap<WireId, WireId>,
+}
+
+impl WireMap {
+    #[inline]
+    fn insert(&mut self, wire: WireId, state: WireStateId, new_wire: WireId) {
+        self.state_wires.insert(state, wire);
+        self.wires.insert(wire, new_wire);
+    }
+
+    #[inline]
+    pub(crate) fn input(&self, state: WireStateId) -> WireId {
+        self.wires[&self.state_wires[&state]]
+    }
+
+    #[inline]
+    pub(crate) fn output(&self, wire: WireId) -> WireId {
+        self.wires[&wire]
+    }
+}
+
+/// The wires each component is connected to
+#[derive(Default)]
+struct Connectivity {
+    inputs: HashMap<ComponentId, Vec<WireId>>,
+    combinational_inputs: HashMap<ComponentId, Vec<WireId>>,
+    outputs: HashMap<ComponentId, Vec<WireId>>,
+    owners: HashMap<OutputStateId, ComponentId>,
+}
+
+impl Connectivity {
+    #[inline]
+    fn inputs(&self, component: ComponentId) -> &[WireId] {
+        self.inputs
+            .get(&component)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The inputs that can change the outputs of a component without a clock edge
+    ///
+    /// For clocked components this is only the clock, all other inputs are sampled on its edges.
+    #[inline]
+    fn combinational_inputs(&self, component: ComponentId) -> &[WireId] {
+        self.combinational_inputs
+            .get(&component)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    fn outputs(&self, component: ComponentId) -> &[WireId] {
+        self.outputs
+            .get(&component)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    fn owner(&self, output: OutputStateId) -> ComponentId {
+        self.owners[&output]
+    }
+}
+
+/// A node in a static evaluation order
+#[derive(Debug, Clone, Copy)]
+enum EvalNode {
+    Wire(WireId),
+    Component(ComponentId),
+}
+
+type ComponentMetadata = HashMap<Arc<str>, Arc<str>>;
+
+/// The first and last output state of a component
+type OutputRange = (OutputStateId, OutputStateId, ComponentId);
+
+/// Finds the component owning an output state in a list of ranges sorted by their first state
+fn find_output_owner(output_ranges: &[OutputRange], output: OutputStateId) -> Option<ComponentId> {
+    let index = output_ranges.partition_point(|&(start, _, _)| start <= output);
+    let (_, end, id) = output_ranges[index.checked_sub(1)?];
+    (output <= end).then_some(id)
+}
+
+/// The parts of the circuit that never change during simulation are behind an `Arc`,
+/// so cloning the data only copies the states and the components, which keep their state inline.
+#[derive(Clone)]
+struct SimulatorData {
+    wires: Arc<WireList>,
+    wire_states: WireStateAllocator,
+
+    components: ComponentStorage,
+    output_states: OutputStateAllocator,
+
+    wire_update_queue: Vec<WireId>,
+    component_update_queue: Vec<ComponentId>,
+
+    wire_names: Arc<HashMap<WireId, Arc<str>>>,
+    component_names: Arc<HashMap<ComponentId, Arc<str>>>,
+    component_metadata: Arc<HashMap<ComponentId, ComponentMetadata>>,
+
+    clock_domains: Arc<HashMap<Arc<str>, ClockDomain>>,
+    ports: Arc<import::ModuleConnections>,
+
+    /// Maps every tied wire to the wire with the lowest ID it is tied to
+    wire_aliases: Arc<HashMap<WireId, WireId>>,
+    /// Maps the original states of tied wires to the state of their root,
+    /// until the components connected to them are updated when building
+    tied_states: HashMap<WireStateId, WireStateId>,
+
+    /// The maximum number of entries a memory component may have
+    max_memory_len: usize,
+
+    /// The range of output states of every component, sorted by the first state and filled in when building
+    output_ranges: Arc<[OutputRange]>,
+    /// The wires every component is connected to, filled in when building
+    connectivity: Arc<Connectivity>,
+}
+
+impl SimulatorData {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            wires: Arc::new(WireList::new()),
+            wire_states: WireStateAllocator::new(),
+
+            components: ComponentStorage::default(),
+            output_states: OutputStateAllocator::new(),
+
+            wire_update_queue: Vec::new(),
+            component_update_queue: Vec::new(),
+
+            wire_names: Arc::default(),
+            component_names: Arc::default(),
+            component_metadata: Arc::default(),
+
+            clock_domains: Arc::default(),
+            ports: Arc::default(),
+
+            wire_aliases: Arc::default(),
+            tied_states: HashMap::new(),
+
+            max_memory_len: DEFAULT_MAX_MEMORY_LEN,
+
+            output_ranges: Arc::new([]),
+            connectivity: Arc::default(),
+        }
+    }
+
+    fn reserve(&mut self, wires: usize, components: usize) {
+        // Most wires and outputs fit into a single word, so use that as the estimate
+        let wire_words = u32::try_from(wires).expect("capacity overflow");
+        let component_words = u32::try_from(components).expect("capacity overflow");
+
+        Arc::make_mut(&mut self.wires).reserve_exact(wires);
+        self.wire_states
+            .reserve_exact(wire_words)
+            .expect("out of memory");
+        self.output_states
+            .reserve_exact(component_words)
+            .expect("out of memory");
+
+        self.wire_update_queue.reserve_exact(wires);
+        self.component_update_queue.reserve_exact(components);
+    }
+
+    #[inline]
+    fn iter_wire_ids(&self) -> impl Iterator<Item = WireId> + '_ {
+        self.wires.ids()
+    }
+
+    #[inline]
+    fn iter_component_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.components.ids()
+    }
+
+    /// Finds the register-like component driving a wire
+    fn find_register(&self, wire: WireId) -> Result<ComponentId, RegisterAccessError> {
+        let wire = self
+            .wires
+            .get(self.wire_root(wire))
+            .ok_or(RegisterAccessError::InvalidWireId)?;
+
+        for &driver in wire.drivers() {
+            if let Some(id) = find_output_owner(&self.output_ranges, driver) {
+                if self.components.register_value(id).is_some() {
+                    return Ok(id);
+                }
+            }
+        }
+
+        Err(RegisterAccessError::NoRegister)
+    }
+
+    fn has_same_topology(&self, other: &Self) -> bool {
+        (self.wire_count() == other.wire_count())
+            && (self.wire_aliases == other.wire_aliases)
+            && self.wires.ids().all(|wire_id| {
+                let wire = self.wires.get(wire_id).unwrap();
+                let other_wire = other.wires.get(wire_id).unwrap();
+                (wire.bit_width() == other_wire.bit_width())
+                    && (wire.drivers() == other_wire.drivers())
+                    && (wire.driving() == other_wire.driving())
+            })
+            && self.components.ids().eq(other.components.ids())
+    }
+
+    fn diff(&self, other: &Self) -> Result<StateDiff, TopologyMismatchError> {
+        if !self.has_same_topology(other) {
+            return Err(TopologyMismatchError);
+        }
+
+        let mut diff = StateDiff::default();
+
+        for wire_id in self.wires.ids() {
+            let [state, _] = self
+                .get_wire_state_and_drive(wire_id)
+                .expect("invalid wire ID");
+            let [other_state, _] = other
+                .get_wire_state_and_drive(wire_id)
+                .expect("invalid wire ID");
+
+            if !state.eq(other_state, state.bit_width()) {
+                diff.wires.push(WireDiff {
+                    id: wire_id,
+                    state: state.to_owned(),
+                    other_state: other_state.to_owned(),
+                });
+            }
+        }
+
+        let memories = self.components.checkpoint_memory();
+        let other_memories = other.components.checkpoint_memory();
+        for component_id in self.components.ids() {
+            let (Some(contents), Some(other_contents)) = (
+                memories.get(&component_id),
+                other_memories.get(&component_id),
+            ) else {
+                continue;
+            };
+
+            if contents != other_contents {
+                let to_owned = |contents: &[InlineLogicState]| {
+                    contents
+                        .iter()
+                        .map(|entry| entry.borrow().to_owned())
+                        .collect()
+                };
+
+                diff.memories.push(MemoryDiff {
+                    id: component_id,
+                    contents: to_owned(contents),
+                    other_contents: to_owned(other_contents),
+                });
+            }
+        }
+
+        Ok(diff)
+    }
+
+    fn iter_wires(&self) -> impl Iterator<Item = WireInfo<'_>> + '_ {
+        self.wires.ids().map(|id| WireInfo {
+            id,
+            name: self.wire_names.get(&id).map(|name| &**name),
+            bit_width: self.wires.get(id).unwrap().bit_width(),
+        })
+    }
+
+    fn iter_components(&self) -> impl Iterator<Item = ComponentInfo<'_>> + '_ {
+        self.components.ids().map(|id| ComponentInfo {
+            id,
+            kind: self.components.kind(id),
+            name: self.component_names.get(&id).map(|name| &**name),
+        })
+    }
+
+    fn iter_component_outputs(
+        &self,
+    ) -> impl Iterator<Item = (ComponentId, WireId, LogicStateRef<'_>)> + '_ {
+        let connectivity = &self.connectivity;
+
+        let mut outputs = Vec::new();
+        for wire_id in self.wires.ids() {
+            let wire = self.wires.get(wire_id).expect("invalid wire ID");
+            for &driver in wire.drivers() {
+                outputs.push((connectivity.owner(driver), driver, wire_id));
+            }
+        }
+        // Sorting by output state as well keeps the outputs of each component in allocation order
+        outputs.sort_unstable();
+
+        outputs.into_iter().map(move |(component, _, wire_id)| {
+            let [state, _] = self
+                .get_wire_state_and_drive(wire_id)
+                .expect("invalid wire ID");
+            (component, wire_id, state)
+        })
+    }
+
+    #[inline]
+    fn wire_count(&self) -> usize {
+        self.wires.wire_count()
+    }
+
+    #[inline]
+    fn component_count(&self) -> usize {
+        self.components.component_count()
+    }
+
+    #[inline]
+    fn wire_root(&self, wire: WireId) -> WireId {
+        self.wire_aliases.get(&wire).copied().unwrap_or(wire)
+    }
+
+    fn tie_wires(&mut self, a: WireId, b: WireId) -> Result<(), AddComponentError> {
+        let wire_a = self.wires.get(a).ok_or(AddComponentError::InvalidWireId)?;
+        let wire_b = self.wires.get(b).ok_or(AddComponentError::InvalidWireId)?;
+        if wire_a.bit_width() != wire_b.bit_width() {
+            return Err(AddComponentError::WireWidthMismatch);
+        }
+
+        let root_a = self.wire_root(a);
+        let root_b = self.wire_root(b);
+        if root_a == root_b {
+            return Ok(());
+        }
+
+        // The wire with the lowest ID becomes the root, so it is always copied before the wires tied to it
+        let (root, alias) = if root_a < root_b {
+            (root_a, root_b)
+        } else {
+            (root_b, root_a)
+        };
+
+        let root_wire = self.wires.get(root).expect("invalid wire ID");
+        let alias_wire = self.wires.get(alias).expect("invalid wire ID");
+        let bit_width = root_wire.bit_width();
+        let root_state = root_wire.state_id();
+        let alias_state = alias_wire.state_id();
+
+        // Tied wires share a single drive, so at most one of them can have one set already
+        let [_, root_drive] = self
+            .wire_states
+            .get(root_state, bit_width)
+            .expect("invalid wire state ID");
+        let [_, alias_drive] = self
+            .wire_states
+            .get(alias_state, bit_width)
+            .expect("invalid wire state ID");
+        let high_z = LogicState::high_z(bit_width);
+        let alias_drive = match (root_drive != high_z, alias_drive != high_z) {
+            (true, true) => return Err(AddComponentError::ConflictingDrives),
+            (false, true) => Some(alias_drive.to_owned()),
+            (_, false) => None,
+        };
+
+        if let Some(alias_drive) = alias_drive {
+            let [_, mut root_drive] = self
+                .wire_states
+                .get_mut(root_state, bit_width)
+                .expect("invalid wire state ID");
+            root_drive.copy_from(&alias_drive);
+        }
+
+        let wires = Arc::make_mut(&mut self.wires);
+        let wire_aliases = Arc::make_mut(&mut self.wire_aliases);
+        for (&wire, target) in wire_aliases.iter_mut() {
+            if *target == alias {
+                *target = root;
+                wires
+                    .get_mut(wire)
+                    .expect("invalid wire ID")
+                    .share_state(root_state);
+            }
+        }
+        wire_aliases.insert(alias, root);
+        wires
+            .get_mut(alias)
+            .expect("invalid wire ID")
+            .share_state(root_state);
+
+        for target in self.tied_states.values_mut() {
+            if *target == alias_state {
+                *target = root_state;
+            }
+        }
+        self.tied_states.insert(alias_state, root_state);
+
+        Ok(())
+    }
+
+    /// Whether any component is still connected to a tied wire instead of its root
+    fn has_pending_ties(&self) -> bool {
+        !self.tied_states.is_empty()
+            || self.wire_aliases.keys().any(|&alias| {
+                let alias = self.wires.get(alias).expect("invalid wire ID");
+                !alias.drivers().is_empty() || !alias.driving().is_empty()
+            })
+    }
+
+    /// Prepares the data for simulation once no more wires or components are added
+    fn finish_build(&mut self) {
+        self.merge_tied_wires();
+        self.output_ranges = self.sorted_output_ranges().into();
+        self.connectivity = Arc::new(self.find_connectivity());
+    }
+
+    /// Moves all connections of tied wires onto their root, so each group of tied wires is simulated as one wire
+    fn merge_tied_wires(&mut self) {
+        if !self.has_pending_ties() {
+            return;
+        }
+
+        let wires = Arc::make_mut(&mut self.wires);
+        for (&alias, &root) in self.wire_aliases.iter() {
+            let (drivers, driving) = wires
+                .get_mut(alias)
+                .expect("invalid wire ID")
+                .take_connections();
+
+            let root = wires.get_mut(root).expect("invalid wire ID");
+            for driver in drivers.iter() {
+                root.add_driver(driver);
+            }
+            for component in driving.iter() {
+                root.add_driving(component);
+            }
+        }
+
+        self.components.remap_wires(&WireRemap {
+            wires: &self.wire_aliases,
+            states: &self.tied_states,
+        });
+        self.tied_states.clear();
+    }
+
+    fn set_wire_drive<'a>(
+        &mut self,
+        wire: WireId,
+        new_drive: impl IntoLogicStateRef<'a>,
+    ) -> Result<(), SetWireDriveError> {
+        let root = self.wire_root(wire);
+        let wire = self.wires.get(root).ok_or(InvalidWireIdError)?;
+        let new_drive = new_drive.into_logic_state_ref();
+        if new_drive.bit_width() != wire.bit_width() {
+            return Err(SetWireDriveError::InvalidBitWidth {
+                expected: wire.bit_width(),
+                actual: new_drive.bit_width(),
+            });
+        }
+
+        if let Some(&driver) = wire.drivers().first() {
+            if new_drive != LogicState::high_z(wire.bit_width()) {
+                // Before building, the output ranges are not cached yet
+                let component = find_output_owner(&self.output_ranges, driver)
+                    .or_else(|| find_output_owner(&self.sorted_output_ranges(), driver))
+                    .expect("output state without component");
+                return Err(SetWireDriveError::DrivenInternally {
+                    wire: root,
+                    component,
+                });
+            }
+        }
+
+        let [_, mut drive] = self
+            .wire_states
+            .get_mut(wire.state_id(), wire.bit_width())
+            .expect("invalid wire state ID");
+
+        let (src_plane_0, src_plane_1) = new_drive.bit_planes();
+        let (dst_plane_0, dst_plane_1) = drive.bit_planes_mut();
+        dst_plane_0.copy_from_slice(src_plane_0);
+        dst_plane_1.copy_from_slice(src_plane_1);
+
+        Ok(())
+    }
+
+    fn get_wire_state_and_drive(
+        &self,
+        wire: WireId,
+    ) -> Result<[LogicStateRef; 2], InvalidWireIdError> {
+        let wire = self
+            .wires
+            .get(self.wire_root(wire))
+            .ok_or(InvalidWireIdError)?;
+        Ok(self
+            .wire_states
+            .get(wire.state_id(), wire.bit_width())
+            .expect("invalid wire state ID"))
+    }
+
+    fn find_multiply_driven_wires(&self) -> Vec<(WireId, SmallVec<[ComponentId; 2]>)> {
+        let connectivity = self.find_connectivity();
+
+        // Tied wires are not merged yet, so their drivers are collected on the root
+        let mut drivers: BTreeMap<WireId, SmallVec<[ComponentId; 2]>> = BTreeMap::new();
+        for wire_id in self.wires.ids() {
+            let wire = self.wires.get(wire_id).expect("invalid wire ID");
+            if wire.drivers().is_empty() {
+                continue;
+            }
+
+            drivers.entry(self.wire_root(wire_id)).or_default().extend(
+                wire.drivers()
+                    .iter()
+                    .map(|&driver| connectivity.owner(driver)),
+            );
+        }
+
+        drivers
+            .into_iter()
+            .filter(|(_, components)| components.len() > 1)
+            .collect()
+    }
+
+    fn wire_driver_contributions(
+        &self,
+        wire: WireId,
+    ) -> Result<Vec<(ComponentId, LogicState)>, InvalidWireIdError> {
+        let wire = self
+            .wires
+            .get(self.wire_root(wire))
+            .ok_or(InvalidWireIdError)?;
+        let connectivity = &self.connectivity;
+
+        Ok(wire
+            .drivers()
+            .iter()
+            .map(|&driver| {
+                let [state] = self
+                    .output_states
+                    .get(driver, wire.bit_width())
+                    .expect("invalid output state ID");
+                (connectivity.owner(driver), state.to_owned())
+            })
+            .collect())
+    }
+
+    //fn get_component_data(
+    //    &self,
+    //    component: ComponentId,
+    //) -> Result<ComponentData<'_, Immutable>, InvalidComponentIdError> {
+    //    self.components
+    //        .get(component)
+    //        .map(Component::get_data)
+    //        .ok_or(InvalidComponentIdError)
+    //}
+
+    //fn get_component_data_mut(
+    //    &mut self,
+    //    component: ComponentId,
+    //) -> Result<ComponentData<'_, Mutable>, InvalidComponentIdError> {
+    //    self.components
+    //        .get_mut(component)
+    //        .map(Component::get_data_mut)
+    //        .ok_or(InvalidComponentIdError)
+    //}
+
+    fn set_wire_name<S: Into<Arc<str>>>(
+        &mut self,
+        wire: WireId,
+        name: S,
+    ) -> Result<(), InvalidWireIdError> {
+        if self.wires.get(wire).is_none() {
+            return Err(InvalidWireIdError);
+        }
+
+        Arc::make_mut(&mut self.wire_names).insert(wire, name.into());
+        Ok(())
+    }
+
+    fn get_wire_name(&self, wire: WireId) -> Result<Option<&str>, InvalidWireIdError> {
+        if self.wires.get(wire).is_none() {
+            return Err(InvalidWireIdError);
+        }
+
+        Ok(self.wire_names.get(&wire).map(|name| &**name))
+    }
+
+    fn set_component_name<S: Into<Arc<str>>>(
+        &mut self,
+        component: ComponentId,
+        name: S,
+    ) -> Result<(), InvalidComponentIdError> {
+        if !self.components.component_exists(component) {
+            return Err(InvalidComponentIdError);
+        }
+
+        Arc::make_mut(&mut self.component_names).insert(component, name.into());
+        Ok(())
+    }
+
+    fn get_component_name(
+        &self,
+        component: ComponentId,
+    ) -> Result<Option<&str>, InvalidComponentIdError> {
+        if !self.components.component_exists(component) {
+            return Err(InvalidComponentIdError);
+        }
+
+        Ok(self.component_names.get(&component).map(|name| &**name))
+    }
+
+    fn set_component_metadata<K: Into<Arc<str>>, V: Into<Arc<str>>>(
+        &mut self,
+        component: ComponentId,
+        key: K,
+        value: V,
+    ) -> Result<(), InvalidComponentIdError> {
+        if !self.components.component_exists(component) {
+            return Err(InvalidComponentIdError);
+        }
+
+        Arc::make_mut(&mut self.component_metadata)
+            .entry(component)
+            .or_default()
+            .insert(key.into(), value.into());
+        Ok(())
+    }
+
+    fn get_component_metadata(
+        &self,
+        component: ComponentId,
+        key: &str,
+    ) -> Result<Option<&str>, InvalidComponentIdError> {
+        if !self.components.component_exists(component) {
+            return Err(InvalidComponentIdError);
+        }
+
+        Ok(self
+            .component_metadata
+            .get(&component)
+            .and_then(|metadata| metadata.get(key))
+            .map(|value| &**value))
+    }
+
+    fn iter_component_metadata(
+        &self,
+        component: ComponentId,
+    ) -> Result<impl Iterator<Item = (&str, &str)> + '_, InvalidComponentIdError> {
+        if !self.components.component_exists(component) {
+            return Err(InvalidComponentIdError);
+        }
+
+        Ok(self
+            .component_metadata
+            .get(&component)
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| (&**key, &**value)))
+    }
+
+    /// Collects the range of output states of every component, sorted by the first state
+    fn sorted_output_ranges(&self) -> Vec<OutputRange> {
+        let mut output_ranges: Vec<_> = self
+            .components
+            .ids()
+            .map(|id| {
+                let (start, end, _) = self.components.output_range(id);
+                (start, end, id)
+            })
+            .collect();
+        output_ranges.sort_unstable_by_key(|&(start, _, _)| start);
+        output_ranges
+    }
+
+    /// Finds the wires every component reads from and drives
+    ///
+    /// Once the simulator is built, the result is available as `self.connectivity`.
+    fn find_connectivity(&self) -> Connectivity {
+        let output_ranges = self.sorted_output_ranges();
+        let find_owner = |output: OutputStateId| find_output_owner(&output_ranges, output);
+
+        let mut connectivity = Connectivity::default();
+        for wire_id in self.wires.ids() {
+            let wire = self.wires.get(wire_id).expect("invalid wire ID");
+
+            for &driver in wire.drivers() {
+                let component = find_owner(driver).expect("output state without component");
+                connectivity.owners.insert(driver, component);
+                connectivity
+                    .outputs
+                    .entry(component)
+                    .or_default()
+                    .push(wire_id);
+            }
+
+            for &component in wire.driving() {
+                connectivity
+                    .inputs
+                    .entry(component)
+                    .or_default()
+                    .push(wire_id);
+
+                let clock = self.components.clock(component);
+                if clock.map_or(true, |clock| clock == wire.state_id()) {
+                    connectivity
+                        .combinational_inputs
+                        .entry(component)
+                        .or_default()
+                        .push(wire_id);
+                }
+            }
+        }
+
+        connectivity
+    }
+
+    /// Sorts all wires and components so that every node comes after all nodes it depends on
+    ///
+    /// Returns `None` if the graph contains a cycle
+    fn eval_order(&self) -> Option<Box<[EvalNode]>> {
+        let connectivity = &self.connectivity;
+
+        let mut wire_dependencies: Vec<_> = self
+            .wires
+            .ids()
+            .map(|wire_id| self.wires.get(wire_id).unwrap().drivers().len())
+            .collect();
+        // Clocked components only depend on their clock, which cuts feedback paths through registers
+        let mut component_dependencies: HashMap<_, _> = connectivity
+            .combinational_inputs
+            .iter()
+            .map(|(&component, inputs)| (component, inputs.len()))
+            .collect();
+
+        // Tied wires are never updated themselves, their root wire updates the shared state
+        let mut ready: Vec<_> = self
+            .wires
+            .ids()
+            .filter(|wire_id| !self.wire_aliases.contains_key(wire_id))
+            .filter(|wire_id| wire_dependencies[wire_id.to_bits() as usize] == 0)
+            .map(EvalNode::Wire)
+            .chain(
+                self.components
+                    .ids()
+                    .filter(|component_id| !component_dependencies.contains_key(component_id))
+                    .map(EvalNode::Component),
+            )
+            .collect();
+
+        // Outputs of clocked components are only propagated once nothing else is ready, so every
+        // clocked component whose clock is known samples its inputs before any of them change
+        let is_clocked_output: Vec<_> = self
+            .wires
+            .ids()
+            .map(|wire_id| {
+                let wire = self.wires.get(wire_id).unwrap();
+                wire.drivers().iter().any(|&driver| {
+                    let owner = connectivity.owner(driver);
+                    self.components.clock(owner).is_some()
+                })
+            })
+            .collect();
+        let mut deferred = Vec::new();
+
+        let mut order = Vec::with_capacity(wire_dependencies.len() + component_dependencies.len());
+        while let Some(node) = ready.pop().or_else(|| deferred.pop()) {
+            order.push(node);
+
+            match node {
+                EvalNode::Wire(wire_id) => {
+                    let wire = self.wires.get(wire_id).expect("invalid wire ID");
+                    for &component in wire.driving() {
+                        if !connectivity
+                            .combinational_inputs(component)
+                            .contains(&wire_id)
+                        {
+                            continue;
+                        }
+
+                        let dependencies = component_dependencies
+                            .get_mut(&component)
+                            .expect("invalid component ID");
+                        *dependencies -= 1;
+                        if *dependencies == 0 {
+                            ready.push(EvalNode::Component(component));
+                        }
+                    }
+                }
+                EvalNode::Component(component_id) => {
+                    for &wire_id in connectivity.outputs(component_id) {
+                        let dependencies = &mut wire_dependencies[wire_id.to_bits() as usize];
+                        *dependencies -= 1;
+                        if *dependencies == 0 {
+                            if is_clocked_output[wire_id.to_bits() as usize] {
+                                deferred.push(EvalNode::Wire(wire_id));
+                            } else {
+                                ready.push(EvalNode::Wire(wire_id));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let node_count = self.wire_count() - self.wire_aliases.len() + self.component_count();
+        (order.len() == node_count).then(|| order.into_boxed_slice())
+    }
+
+    fn extract_cone(
+        &self,
+        output: WireId,
+    ) -> Result<(SimulatorBuilder, HashMap<WireId, WireId>), InvalidWireIdError> {
+        self.wires.get(output).ok_or(InvalidWireIdError)?;
+        let connectivity = &self.connectivity;
+
+        let mut cone_wires = HashSet::new();
+        let mut cone_components = HashSet::new();
+        let mut register_outputs = Vec::new();
+        let mut pending = vec![output];
+        while let Some(wire_id) = pending.pop() {
+            if !cone_wires.insert(wire_id) {
+                continue;
+            }
+
+            // A tied wire is driven through its root
+            pending.push(self.wire_root(wire_id));
+
+            let wire = self.wires.get(wire_id).expect("invalid wire ID");
+            for &driver in wire.drivers() {
+                let component = connectivity.owner(driver);
+
+                // Clocked components end the cone, their outputs become inputs of the new circuit
+                if self.components.clock(component).is_some() {
+                    register_outputs.push(wire_id);
+                    continue;
+                }
+
+                if cone_components.insert(component) {
+                    pending.extend_from_slice(connectivity.inputs(component));
+                    pending.extend_from_slice(connectivity.outputs(component));
+                }
+            }
+        }
+
+        let mut cone_wires: Vec<_> = cone_wires.into_iter().collect();
+        cone_wires.sort_unstable();
+        let mut cone_components: Vec<_> = cone_components.into_iter().collect();
+        cone_components.sort_unstable();
+
+        let (mut builder, wire_map) = self.copy_subgraph(cone_wires, cone_components);
+        for wire_id in register_outputs {
+            let [state, _] = self
+                .get_wire_state_and_drive(wire_id)
+                .expect("invalid wire ID");
+            builder
+                .set_wire_drive(wire_map.wires[&wire_id], state)
+                .expect("invalid wire ID");
+        }
+
+        Ok((builder, wire_map.wires))
+    }
+
+    /// Copies the given wires and components into a new circuit
+    ///
+    /// Wires and components are added in the given order, so copying all of them in ID order preserves their IDs
+    fn copy_subgraph(
+        &self,
+        wires: impl IntoIterator<Item = WireId>,
+        components: impl IntoIterator<Item = ComponentId>,
+    ) -> (SimulatorBuilder, WireMap) {
+        let mut builder = SimulatorBuilder::default();
+        let (wire_map, _) = self.copy_subgraph_into(&mut builder, wires, components);
+        (builder, wire_map)
+    }
+
+    /// Copies the given wires and components into an existing circuit
+    ///
+    /// Also returns a map from the copied components to their new IDs
+    fn copy_subgraph_into(
+        &self,
+        builder: &mut SimulatorBuilder,
+        wires: impl IntoIterator<Item = WireId>,
+        components: impl IntoIterator<Item = ComponentId>,
+    ) -> (WireMap, HashMap<ComponentId, ComponentId>) {
+        let mut wire_map = WireMap::default();
+        let mut component_map = HashMap::new();
+        for wire_id in wires {
+            let wire = self.wires.get(wire_id).expect("invalid wire ID");
+            let new_id = builder
+                .add_wire(wire.bit_width())
+                .expect("wire limit reached");
+            wire_map.insert(wire_id, wire.state_id(), new_id);
+
+            if let Some(name) = self.wire_names.get(&wire_id) {
+                builder
+                    .set_wire_name(new_id, Arc::clone(name))
+                    .expect("invalid wire ID");
+            }
+        }
+
+        // Copies of tied wires are tied as well, before their shared drive is set
+        for (&wire_id, &new_id) in &wire_map.wires {
+            let root = self.wire_root(wire_id);
+            if root == wire_id {
+                continue;
+            }
+
+            if let Some(&new_root) = wire_map.wires.get(&root) {
+                builder
+                    .tie_wires(new_root, new_id)
+                    .expect("failed to tie wires");
+            }
+        }
+
+        for (&wire_id, &new_id) in &wire_map.wires {
+            let [_, drive] = self
+                .get_wire_state_and_drive(wire_id)
+                .expect("invalid wire ID");
+            builder
+                .set_wire_drive(new_id, drive)
+                .expect("invalid wire ID");
+        }
+
+        for component_id in components {
+            let new_id = self
+                .components
+                .copy_component_into(component_id, builder, &wire_map)
+                .expect("failed to copy component");
+            if let Some(name) = self.component_names.get(&component_id) {
+                Arc::make_mut(&mut builder.data.component_names).insert(new_id, Arc::clone(name));
+            }
+            if let Some(metadata) = self.component_metadata.get(&component_id) {
+                Arc::make_mut(&mut builder.data.component_metadata)
+                    .insert(new_id, metadata.clone());
+            }
+            component_map.insert(component_id, new_id);
+        }
+
+        (wire_map, component_map)
+    }
+
+    fn dump_state(&self, format: ValueFormat) -> String {
+        use itertools::Itertools;
+        use std::fmt::Write;
+
+        let connectivity = &self.connectivity;
+        let mut report = String::new();
+
+        writeln!(report, "Wires:").unwrap();
+        for wire_id in self.wires.ids() {
+            let wire = self.wires.get(wire_id).expect("invalid wire ID");
+            let [state, drive] = self
+                .get_wire_state_and_drive(wire_id)
+                .expect("invalid wire ID");
+            let name = self.wire_names.get(&wire_id).map_or("", |name| &**name);
+
+            writeln!(
+                report,
+                "    W{} {name:?} [{}]: state {}, drive {}",
+                wire_id.to_bits(),
+                wire.bit_width(),
+                format.format(state),
+                format.format(drive),
+            )
+            .unwrap();
+        }
+
+        writeln!(report, "Components:").unwrap();
+        for component_id in self.components.ids() {
+            let name = self
+                .component_names
+                .get(&component_id)
+                .map_or("", |name| &**name);
+
+            write!(
+                report,
+                "    C{} {} {name:?}:",
+                component_id.to_bits(),
+                self.components.kind_name(component_id),
+            )
+            .unwrap();
+
+            for &wire_id in connectivity.outputs(component_id).iter().dedup() {
+                let wire = self.wires.get(wire_id).expect("invalid wire ID");
+                for &driver in wire.drivers() {
+                    if connectivity.owner(driver) == component_id {
+                        let [output_state] = self
+                            .output_states
+                            .get(driver, wire.bit_width())
+                            .expect("invalid output state ID");
+                        write!(
+                            report,
+                            " {} -> W{}",
+                            format.format(output_state),
+                            wire_id.to_bits(),
+                        )
+                        .unwrap();
+                    }
+                }
+            }
+
+            writeln!(report).unwrap();
+        }
+
+        report
+    }
+
+    /// Updates every node exactly once in the given order
+    ///
+    /// If a conflict log is given, conflicting wires are resolved to Undefined and logged instead of producing an error.
+    fn run_ordered(
+        &mut self,
+        eval_order: &[EvalNode],
+        mut conflict_log: Option<&mut Vec<WireId>>,
+    ) -> SimulationRunResult {
+        let mut conflicts = Vec::new();
+
+        for &node in eval_order {
+            match node {
+                EvalNode::Wire(wire_id) => {
+                    let wire = self.wires.get(wire_id).expect("invalid wire ID");
+                    let states = self.wire_states.range_mut(
+                        wire.state_id(),
+                        wire.state_id(),
+                        wire.bit_width(),
+                    );
+
+                    if let WireUpdateResult::Conflict =
+                        wire.update(states, self.output_states.view())
+                    {
+                        if let Some(conflict_log) = conflict_log.as_deref_mut() {
+                            let states = self.wire_states.range_mut(
+                                wire.state_id(),
+                                wire.state_id(),
+                                wire.bit_width(),
+                            );
+
+                            let _ = wire.resolve_conflict(states);
+                            conflict_log.push(wire_id);
+                        } else {
+                            conflicts.push(wire_id);
+                        }
+                    }
+                }
+                EvalNode::Component(component_id) => {
+                    let _ = unsafe {
+                        // SAFETY: components are updated one at a time, so the ID is unique
+                        self.components.update_component(
+                            component_id,
+                            self.wire_states.view(),
+                            &self.output_states,
+                        )
+                    };
+                }
+            }
+        }
+
+        if conflicts.is_empty() {
+            SimulationRunResult::Ok
+        } else {
+            SimulationRunResult::Err(SimulationErrors {
+                conflicts: conflicts.into_boxed_slice(),
+            })
+        }
+    }
+
+    fn stats(&self) -> SimulationStats {
+        todo!()
+        //    let (small_component_count, large_component_count) = self.components.component_counts();
+
+        //    SimulationStats {
+        //        wire_count: self.wires.wire_count(),
+        //        wire_alloc_size: self.wires.alloc_size(),
+        //        wire_width_alloc_size: self.wire_states.width_alloc_size(),
+        //        wire_drive_alloc_size: self.wire_states.drive_alloc_size(),
+        //        wire_state_alloc_size: self.wire_states.state_alloc_size(),
+        //        small_component_count,
+        //        large_component_count,
+        //        component_alloc_size: self.components.alloc_size(),
+        //        large_component_alloc_size: self.components.large_alloc_size(),
+        //        output_width_alloc_size: self.output_states.width_alloc_size(),
+        //        output_state_alloc_size: self.output_states.state_alloc_size(),
+        //    }
+    }
+
+    #[cfg(feature = "dot-export")]
+    fn write_dot<W: std::io::Write>(
+        &self,
+        mut writer: W,
+        show_states: bool,
+    ) -> std::io::Result<()> {
+        todo!()
+        //    writeln!(writer, "digraph {{")?;
+
+        //    let mut wire_state_map = HashMap::new();
+        //    for wire_id in self.wires.ids() {
+        //        let wire = &self.wires.get(wire_id).unwrap();
+        //        let width = self.wire_states.get_width(wire.state);
+        //        wire_state_map.insert(wire.state, wire_id);
+
+        //        #[allow(clippy::collapsible_else_if)]
+        //        if show_states {
+        //            if let Some(name) = self.wire_names.get(&wire_id) {
+        //                let state = self.get_wire_state(wire_id).unwrap().display_string(width);
+        //                if &**name == state.as_str() {
+        //                    // Don't print constant wire states twice
+        //                    writeln!(
+        //                        writer,
+        //                        "    W{}[label=\"{}\" shape=\"diamond\"];",
+        //                        wire_id.to_bits(),
+        //                        name,
+        //                    )?;
+        //                } else {
+        //                    writeln!(
+        //                        writer,
+        //                        "    W{}[label=\"{} ({})\" shape=\"diamond\"];",
+        //                        wire_id.to_bits(),
+        //                        name,
+        //                        state,
+        //                    )?;
+        //                }
+        //            } else {
+        //                writeln!(
+        //                    writer,
+        //                    "    W{}[label=\"{}\" shape=\"diamond\"];",
+        //                    wire_id.to_bits(),
+        //                    self.get_wire_state(wire_id).unwrap().display_string(width),
+        //                )?;
+        //            }
+        //        } else {
+        //            if let Some(name) = self.wire_names.get(&wire_id) {
         //                writeln!(
         //                    writer,
         //                    "    W{}[label=\"{} [{}]\" shape=\"diamond\"];",
@@ -629,6 +2054,10 @@ pub struct Simulator<VCD: std::io::Write = std::io::Sink> {
     data: SimulatorData,
     #[allow(dead_code)]
     vcd: VCD,
+    eval_order: Option<Box<[EvalNode]>>,
+    start_order: Option<Box<[EvalNode]>>,
+    step_histogram: Option<BTreeMap<u64, u64>>,
+    settled: bool,
 }
 
 impl<VCD: std::io::Write> Simulator<VCD> {
@@ -644,16 +2073,58 @@ impl<VCD: std::io::Write> Simulator<VCD> {
         self.data.iter_component_ids()
     }
 
+    /// Iterates over all wires in the graph together with their names and widths
+    #[inline]
+    pub fn iter_wires(&self) -> impl Iterator<Item = WireInfo<'_>> + '_ {
+        self.data.iter_wires()
+    }
+
+    /// Iterates over all components in the graph together with their kinds and names
+    #[inline]
+    pub fn iter_components(&self) -> impl Iterator<Item = ComponentInfo<'_>> + '_ {
+        self.data.iter_components()
+    }
+
+    /// Iterates over the output wires of all components together with their current states
+    ///
+    /// Components with multiple outputs yield one item per output, in the order of their outputs.
+    pub fn iter_component_outputs(
+        &self,
+    ) -> impl Iterator<Item = (ComponentId, WireId, LogicStateRef<'_>)> + '_ {
+        self.data.iter_component_outputs()
+    }
+
+    /// The number of wires in the graph
+    #[inline]
+    pub fn wire_count(&self) -> usize {
+        self.data.wire_count()
+    }
+
+    /// The number of components in the graph
+    #[inline]
+    pub fn component_count(&self) -> usize {
+        self.data.component_count()
+    }
+
+    /// Checks whether the graph contains neither wires nor components
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        (self.wire_count() == 0) && (self.component_count() == 0)
+    }
+
     /// Drives a wire to a certain state without needing a component
     ///
-    /// Any unspecified bits will be set to Z
+    /// The new drive must have the same width as the wire.
+    /// Wires driven by a component can only be released to high impedance.
     #[inline]
     pub fn set_wire_drive<'a>(
         &mut self,
         wire: WireId,
         new_drive: impl IntoLogicStateRef<'a>,
-    ) -> Result<(), InvalidWireIdError> {
-        self.data.set_wire_drive(wire, new_drive)
+    ) -> Result<(), SetWireDriveError> {
+        self.data.set_wire_drive(wire, new_drive)?;
+        self.settled = false;
+        Ok(())
     }
 
     /// Gets the current state of a wire
@@ -661,8 +2132,27 @@ impl<VCD: std::io::Write> Simulator<VCD> {
     pub fn get_wire_state_and_drive(
         &self,
         wire: WireId,
-    ) -> Result<[LogicStateRef; 2], InvalidWireIdError> {
-        self.data.get_wire_state_and_drive(wire)
+    ) -> Result<[LogicStateRef; 2], InvalidWireIdError> {
+        self.data.get_wire_state_and_drive(wire)
+    }
+
+    /// Gets the current state of a single bit of a wire
+    #[inline]
+    pub fn get_wire_bit(&self, wire: WireId, index: u32) -> Result<LogicBitState, GetWireBitError> {
+        let [state, _] = self.data.get_wire_state_and_drive(wire)?;
+        state.bit(index).ok_or(GetWireBitError::IndexOutOfRange)
+    }
+
+    /// Gets the values the components driving a wire currently output onto it
+    ///
+    /// Unlike the state returned by [Simulator::get_wire_state_and_drive], these are the values before
+    /// they are combined with each other and the drive of the wire, which helps to find the cause of a conflict.
+    /// Contributions are listed in the order the drivers are combined in.
+    pub fn wire_driver_contributions(
+        &self,
+        wire: WireId,
+    ) -> Result<Vec<(ComponentId, LogicState)>, InvalidWireIdError> {
+        self.data.wire_driver_contributions(wire)
     }
 
     ///// Gets a components data
@@ -698,12 +2188,58 @@ impl<VCD: std::io::Write> Simulator<VCD> {
         self.data.get_component_name(component)
     }
 
+    /// Gets the metadata stored under `key` for a component, if any has been assigned
+    #[inline]
+    pub fn get_component_metadata(
+        &self,
+        component: ComponentId,
+        key: &str,
+    ) -> Result<Option<&str>, InvalidComponentIdError> {
+        self.data.get_component_metadata(component, key)
+    }
+
+    /// Iterates over all metadata assigned to a component as key-value pairs, in no particular order
+    #[inline]
+    pub fn iter_component_metadata(
+        &self,
+        component: ComponentId,
+    ) -> Result<impl Iterator<Item = (&str, &str)> + '_, InvalidComponentIdError> {
+        self.data.iter_component_metadata(component)
+    }
+
+    /// Builds a standalone circuit containing only the fan-in cone of a wire
+    ///
+    /// All components driving the wire are copied, recursively following their inputs until
+    /// wires without drivers are reached, which become the inputs of the new circuit and keep their drive.
+    /// Clocked components are not copied, the wires they drive become inputs driven with their current state instead.
+    /// Also returns a map from the wires of this circuit to the wires of the new one.
+    pub fn extract_cone(
+        &self,
+        output: WireId,
+    ) -> Result<(SimulatorBuilder, HashMap<WireId, WireId>), InvalidWireIdError> {
+        self.data.extract_cone(output)
+    }
+
     /// Collects statistics of the simulation
     #[inline]
     pub fn stats(&self) -> SimulationStats {
         self.data.stats()
     }
 
+    /// Creates a human-readable report of the current state of all wires and components
+    ///
+    /// Lists the name, width, state and drive of every wire, as well as the kind, name and output states of every component.
+    #[inline]
+    pub fn dump_state(&self) -> String {
+        self.data.dump_state(ValueFormat::Binary)
+    }
+
+    /// Creates a report like [Simulator::dump_state], formatting all values as specified
+    #[inline]
+    pub fn dump_state_with_format(&self, format: ValueFormat) -> String {
+        self.data.dump_state(format)
+    }
+
     /// Writes the simulation graph into a Graphviz DOT file
     #[cfg(feature = "dot-export")]
     #[inline]
@@ -731,11 +2267,19 @@ Simulation algorithm:
 
 */
 impl<VCD: std::io::Write> Simulator<VCD> {
-    fn update_wires(&mut self) -> SimulationStepResult {
+    fn update_wires(&mut self, conflict_log: Option<&mut Vec<WireId>>) -> SimulationStepResult {
         use rayon::prelude::*;
 
         self.data.component_update_queue.clear();
 
+        sim_log!(
+            trace,
+            "updating {} wires",
+            self.data.wire_update_queue.len()
+        );
+
+        let resolve_conflicts = conflict_log.is_some();
+
         let conflicts = Mutex::new(Vec::new());
 
         let perform = |wire_id| {
@@ -755,13 +2299,34 @@ impl<VCD: std::io::Write> Simulator<VCD> {
 
             match wire.update(states, self.data.output_states.view()) {
                 WireUpdateResult::Unchanged => [].as_slice(),
-                WireUpdateResult::Changed => wire.driving(),
+                WireUpdateResult::Changed => {
+                    sim_log!(trace, "wire {} changed", wire_id.to_bits());
+                    wire.driving()
+                }
                 WireUpdateResult::Conflict => {
+                    sim_log!(trace, "wire {} has conflicting drivers", wire_id.to_bits());
+
                     // Locking here is ok because we are in the error path
                     let mut conflict_list = conflicts.lock().expect("failed to aquire mutex");
                     conflict_list.push(wire_id);
 
-                    [].as_slice()
+                    if resolve_conflicts {
+                        let states = unsafe {
+                            // SAFETY: since the wire is unique, so is its state
+                            self.data.wire_states.range_unsafe(
+                                wire.state_id(),
+                                wire.state_id(),
+                                wire.bit_width(),
+                            )
+                        };
+
+                        match wire.resolve_conflict(states) {
+                            WireUpdateResult::Unchanged => [].as_slice(),
+                            _ => wire.driving(),
+                        }
+                    } else {
+                        [].as_slice()
+                    }
                 }
             }
         };
@@ -788,9 +2353,13 @@ impl<VCD: std::io::Write> Simulator<VCD> {
             .expect("failed to aquire mutex")
             .into_boxed_slice();
 
-        if !conflicts.is_empty() {
-            SimulationStepResult::Err(SimulationErrors { conflicts })
-        } else if self.data.component_update_queue.is_empty() {
+        if let Some(conflict_log) = conflict_log {
+            conflict_log.extend_from_slice(&conflicts);
+        } else if !conflicts.is_empty() {
+            return SimulationStepResult::Err(SimulationErrors { conflicts });
+        }
+
+        if self.data.component_update_queue.is_empty() {
             SimulationStepResult::Unchanged
         } else {
             SimulationStepResult::Changed
@@ -802,6 +2371,12 @@ impl<VCD: std::io::Write> Simulator<VCD> {
 
         self.data.wire_update_queue.clear();
 
+        sim_log!(
+            trace,
+            "updating {} components",
+            self.data.component_update_queue.len()
+        );
+
         let perform = |component_id| {
             unsafe {
                 // SAFETY: `sort_unstable` + `dedup` ensure the ID is unique between all iterations
@@ -838,20 +2413,250 @@ impl<VCD: std::io::Write> Simulator<VCD> {
     }
 
     /// Resets the simulation
+    ///
+    /// Wire drives set using [Simulator::set_wire_drive] or [SimulatorBuilder::set_wire_drive] are kept,
+    /// use [Simulator::reset_all] to release them as well.
     pub fn reset(&mut self) {
         self.data.wire_states.clear_states();
         self.data.output_states.clear_states();
 
         self.data.components.reset_components();
+        self.settled = false;
+    }
+
+    /// Resets the simulation and releases all wire drives back to high impedance
+    pub fn reset_all(&mut self) {
+        self.data.wire_states.clear_all_states();
+        self.data.output_states.clear_states();
+
+        self.data.components.reset_components();
+        self.settled = false;
+    }
+
+    /// Captures the contents of all memory components, e.g. the entries of FIFOs
+    ///
+    /// Unlike a full copy of the simulation, wire states, output states and the state of
+    /// clock edge detection are not captured.
+    pub fn checkpoint_memory(&self) -> MemoryCheckpoint {
+        MemoryCheckpoint {
+            contents: self.data.components.checkpoint_memory(),
+        }
+    }
+
+    /// The wires marked as primary inputs and outputs using [SimulatorBuilder::mark_input] and [SimulatorBuilder::mark_output]
+    #[inline]
+    pub fn ports(&self) -> &import::ModuleConnections {
+        &self.data.ports
+    }
+
+    /// Reads the value stored in the register driving `wire`
+    ///
+    /// Registers are components that store a single value and show it on their output, e.g. `Register` and `Delay`.
+    pub fn read_register_by_output(&self, wire: WireId) -> Result<LogicState, RegisterAccessError> {
+        let register = self.data.find_register(wire)?;
+        let value = self
+            .data
+            .components
+            .register_value(register)
+            .expect("component is not a register");
+        Ok(value.to_owned())
+    }
+
+    /// Replaces the value stored in the register driving `wire`
+    ///
+    /// The output of the register shows the new value after the next call to [Simulator::run_sim].
+    pub fn write_register_by_output<'a>(
+        &mut self,
+        wire: WireId,
+        value: impl IntoLogicStateRef<'a>,
+    ) -> Result<(), RegisterAccessError> {
+        let register = self.data.find_register(wire)?;
+        let value = value.into_logic_state_ref();
+        let register_width = self
+            .data
+            .components
+            .register_value(register)
+            .expect("component is not a register")
+            .bit_width();
+        if value.bit_width() != register_width {
+            return Err(RegisterAccessError::WireWidthMismatch);
+        }
+
+        self.data.components.set_register_value(register, value);
+        self.settled = false;
+        Ok(())
+    }
+
+    /// Computes a hash over the states of all wires
+    ///
+    /// The fingerprint changes whenever the state of any wire changes, so it can be compared between
+    /// calls to cheaply detect whether anything happened, e.g. to skip redrawing a GUI.
+    /// It is a fast non-cryptographic hash meant for change detection only: different states can
+    /// collide, and the value is not stable across versions of this crate.
+    pub fn state_fingerprint(&self) -> u64 {
+        use std::hash::Hasher;
+
+        let mut hasher = ahash::AHasher::default();
+        self.data.wire_states.hash_bit_planes(0, &mut hasher);
+        hasher.finish()
+    }
+
+    /// Checks whether the simulation is settled, i.e. another step would not change any wire
+    ///
+    /// The simulation is settled after a run that returned [SimulationRunResult::Ok], until it is
+    /// changed from outside again, e.g. by [Simulator::set_wire_drive], [Simulator::reset] or
+    /// [Simulator::restore_memory]. This does not evaluate anything, so it is cheap enough to poll.
+    #[inline]
+    pub fn is_settled(&self) -> bool {
+        self.settled
+    }
+
+    /// Compares the state of this simulator to another one built from the same netlist
+    ///
+    /// Both simulators must contain the same wires with the same widths and the same components,
+    /// otherwise an error is returned. Wire drives are not compared, only the resolved states.
+    pub fn diff(&self, other: &Self) -> Result<StateDiff, TopologyMismatchError> {
+        self.data.diff(&other.data)
+    }
+
+    /// Restores the contents of all memory components to a checkpoint created by [Simulator::checkpoint_memory]
+    ///
+    /// The checkpoint must have been created by a simulator with the same memory components, otherwise
+    /// an error is returned and the simulation is left unchanged.
+    /// Outputs that depend on the restored contents are updated by the next call to [Simulator::run_sim].
+    pub fn restore_memory(
+        &mut self,
+        checkpoint: &MemoryCheckpoint,
+    ) -> Result<(), TopologyMismatchError> {
+        if !self.data.components.memory_fits(&checkpoint.contents) {
+            return Err(TopologyMismatchError);
+        }
+
+        self.data.components.restore_memory(&checkpoint.contents);
+        self.settled = false;
+        Ok(())
+    }
+
+    /// Writes the contents of all memory components, e.g. FIFOs and registers, to a flat image
+    ///
+    /// The image is versioned and lists the components in the order of their IDs, so it can be
+    /// loaded by [Simulator::load_state_image] into any simulator built from the same circuit.
+    /// Like [Simulator::checkpoint_memory], wire states are not included.
+    pub fn save_state_image<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<()> {
+        let layouts = self.data.components.memory_layouts();
+        let contents = self.data.components.checkpoint_memory();
+
+        writer.write_all(&STATE_IMAGE_MAGIC)?;
+        writer.write_all(&STATE_IMAGE_VERSION.to_le_bytes())?;
+        writer.write_all(&(layouts.len() as u32).to_le_bytes())?;
+
+        for (id, bit_width, _) in layouts {
+            let memory = &contents[&id];
+
+            writer.write_all(&id.to_bits().to_le_bytes())?;
+            writer.write_all(&bit_width.get().to_le_bytes())?;
+            writer.write_all(&(memory.len() as u32).to_le_bytes())?;
+
+            for entry in memory {
+                let (plane_0, plane_1) = entry.bit_planes();
+                for &word in plane_0.iter().chain(plane_1) {
+                    writer.write_all(&word.to_le_bytes())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the contents of all memory components with an image written by [Simulator::save_state_image]
+    ///
+    /// The whole image is validated against the memory components of this simulator before anything is
+    /// replaced, so on error the simulation is left unchanged.
+    /// Outputs that depend on the loaded contents are updated by the next call to [Simulator::run_sim].
+    pub fn load_state_image<R: std::io::Read>(
+        &mut self,
+        mut reader: R,
+    ) -> Result<(), StateImageError> {
+        fn read_u32(reader: &mut impl std::io::Read) -> std::io::Result<u32> {
+            let mut bytes = [0; 4];
+            reader.read_exact(&mut bytes)?;
+            Ok(u32::from_le_bytes(bytes))
+        }
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != STATE_IMAGE_MAGIC {
+            return Err(StateImageError::InvalidFormat);
+        }
+
+        let version = read_u32(&mut reader)?;
+        if version != STATE_IMAGE_VERSION {
+            return Err(StateImageError::UnsupportedVersion(version));
+        }
+
+        let layouts = self.data.components.memory_layouts();
+        if (read_u32(&mut reader)? as usize) != layouts.len() {
+            return Err(StateImageError::LayoutMismatch);
+        }
+
+        let mut contents = HashMap::with_capacity(layouts.len());
+        for (id, bit_width, lens) in layouts {
+            if (read_u32(&mut reader)? != id.to_bits())
+                || (read_u32(&mut reader)? != bit_width.get())
+            {
+                return Err(StateImageError::LayoutMismatch);
+            }
+
+            let len = read_u32(&mut reader)? as usize;
+            if !lens.contains(&len) {
+                return Err(StateImageError::LayoutMismatch);
+            }
+
+            let mut memory = Vec::with_capacity(len);
+            for _ in 0..len {
+                let mut entry = InlineLogicState::undefined(bit_width);
+                let (plane_0, plane_1) = entry.bit_planes_mut();
+                for word in plane_0.iter_mut().chain(plane_1) {
+                    *word = read_u32(&mut reader)?;
+                }
+
+                memory.push(entry);
+            }
+
+            contents.insert(id, memory);
+        }
+
+        if reader.read(&mut [0])? != 0 {
+            return Err(StateImageError::InvalidFormat);
+        }
+
+        self.data.components.restore_memory(&contents);
+        self.settled = false;
+        Ok(())
     }
 
-    fn begin_sim(&mut self) -> SimulationStepResult {
+    fn begin_sim(&mut self, conflict_log: Option<&mut Vec<WireId>>) -> SimulationStepResult {
+        if let Some(start_order) = &self.start_order {
+            // Updating every node once in dependency order settles an acyclic circuit in a single pass
+            self.data.wire_update_queue.clear();
+            self.data.component_update_queue.clear();
+            return match self.data.run_ordered(start_order, conflict_log) {
+                SimulationRunResult::Err(err) => SimulationStepResult::Err(err),
+                _ => SimulationStepResult::Unchanged,
+            };
+        }
+
         // We have to perform the first update step on all nodes in the graph,
         // so we insert all IDs into the queues.
 
         self.data.wire_update_queue.clear();
-        self.data.wire_update_queue.extend(self.data.wires.ids());
-        if let SimulationStepResult::Err(err) = self.update_wires() {
+        self.data.wire_update_queue.extend(
+            self.data
+                .wires
+                .ids()
+                .filter(|wire_id| !self.data.wire_aliases.contains_key(wire_id)),
+        );
+        if let SimulationStepResult::Err(err) = self.update_wires(conflict_log) {
             return SimulationStepResult::Err(err);
         }
 
@@ -862,32 +2667,279 @@ impl<VCD: std::io::Write> Simulator<VCD> {
         self.update_components()
     }
 
-    fn step_sim(&mut self) -> SimulationStepResult {
-        match self.update_wires() {
+    fn step_sim(&mut self, conflict_log: Option<&mut Vec<WireId>>) -> SimulationStepResult {
+        match self.update_wires(conflict_log) {
             SimulationStepResult::Unchanged => SimulationStepResult::Unchanged,
             SimulationStepResult::Changed => self.update_components(),
             SimulationStepResult::Err(err) => SimulationStepResult::Err(err),
         }
     }
 
-    /// Runs the simulation until it settles, but at most for `max_steps` steps
-    pub fn run_sim(&mut self, max_steps: u64) -> SimulationRunResult {
+    /// Runs the simulation until it settles, calling `on_step` after every step
+    ///
+    /// Returns `None` instead of a result if `on_step` stopped the run.
+    fn run_until_settled(
+        &mut self,
+        max_steps: u64,
+        mut conflict_log: Option<&mut Vec<WireId>>,
+        mut on_step: impl FnMut(SimulationProgress) -> ControlFlow<()>,
+    ) -> (Option<SimulationRunResult>, u64) {
         let mut steps = 0;
-        let mut result = self.begin_sim();
+        let mut result = self.begin_sim(conflict_log.as_deref_mut());
         loop {
             match result {
-                SimulationStepResult::Unchanged => return SimulationRunResult::Ok,
+                SimulationStepResult::Unchanged => {
+                    sim_log!(debug, "settled after {} steps", steps + 1);
+                    return (Some(SimulationRunResult::Ok), steps + 1);
+                }
                 SimulationStepResult::Changed => {
                     if steps > max_steps {
-                        return SimulationRunResult::MaxStepsReached;
+                        sim_log!(debug, "did not settle within {max_steps} steps");
+                        return (Some(SimulationRunResult::MaxStepsReached), steps + 1);
                     }
 
                     steps += 1;
-                    result = self.step_sim();
+                    sim_log!(debug, "step {steps}");
+                    result = self.step_sim(conflict_log.as_deref_mut());
+
+                    let progress = SimulationProgress {
+                        steps,
+                        queued_wires: self.data.wire_update_queue.len(),
+                        queued_components: self.data.component_update_queue.len(),
+                    };
+
+                    if on_step(progress).is_break() {
+                        sim_log!(debug, "stopped after {steps} steps");
+                        return (None, steps + 1);
+                    }
                 }
-                SimulationStepResult::Err(err) => return SimulationRunResult::Err(err),
+                SimulationStepResult::Err(err) => {
+                    sim_log!(debug, "driver conflict in step {}", steps + 1);
+                    return (Some(SimulationRunResult::Err(err)), steps + 1);
+                }
+            }
+        }
+    }
+
+    /// Runs the simulation until it settles, without a way to stop it early
+    fn settle(
+        &mut self,
+        max_steps: u64,
+        conflict_log: Option<&mut Vec<WireId>>,
+    ) -> (SimulationRunResult, u64) {
+        let (result, steps) =
+            self.run_until_settled(max_steps, conflict_log, |_| ControlFlow::Continue(()));
+        (result.expect("the step hook never stops the run"), steps)
+    }
+
+    /// Runs the simulation until it settles, but at most for `max_steps` steps
+    ///
+    /// If the simulator was created using [SimulatorBuilder::build_acyclic],
+    /// a single ordered pass is performed instead and `max_steps` is ignored
+    pub fn run_sim(&mut self, max_steps: u64) -> SimulationRunResult {
+        let (result, steps) = if let Some(eval_order) = &self.eval_order {
+            (self.data.run_ordered(eval_order, None), 1)
+        } else {
+            self.settle(max_steps, None)
+        };
+
+        self.finish_run(&result, steps);
+        result
+    }
+
+    /// Runs the simulation like [Simulator::run_sim], but accepts a circuit that is still changing after `min_steps` steps
+    ///
+    /// This is meant for circuits containing free-running clocks, which never settle and would otherwise be
+    /// reported as oscillating. The run ends as soon as the simulation settles or `min_steps` steps have been
+    /// performed, and returns `Ok` in both cases. Only in the latter case is the simulation not considered settled
+    /// afterwards, and the next run continues from where this one stopped. If `max_steps` is lower than `min_steps`,
+    /// `MaxStepsReached` is returned if the simulation is still changing after `max_steps` steps, like in [Simulator::run_sim].
+    ///
+    /// If the simulator was created using [SimulatorBuilder::build_acyclic], a single ordered pass
+    /// is performed instead and both step counts are ignored.
+    pub fn run_sim_min_steps(&mut self, min_steps: u64, max_steps: u64) -> SimulationRunResult {
+        if let Some(eval_order) = &self.eval_order {
+            let result = self.data.run_ordered(eval_order, None);
+            self.finish_run(&result, 1);
+            return result;
+        }
+
+        // The first step is performed before the hook is called for the first time
+        let (result, steps) = self.run_until_settled(max_steps, None, |progress| {
+            if (progress.steps + 1) >= min_steps {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+        match result {
+            Some(result) => {
+                self.finish_run(&result, steps);
+                result
+            }
+            None => {
+                sim_log!(debug, "still changing after {steps} steps");
+                self.finish_run(&SimulationRunResult::Ok, steps);
+                self.settled = false;
+                SimulationRunResult::Ok
+            }
+        }
+    }
+
+    /// Evaluates a purely combinational circuit in a single pass
+    ///
+    /// Instead of iterating until the circuit settles, every wire and component is updated exactly once
+    /// in dependency order. Afterwards all components are updated one more time to check that no wire changes anymore.
+    /// Fails if the circuit contains combinational cycles, did not settle or had driver conflicts.
+    pub fn evaluate_combinational(&mut self) -> Result<(), NotCombinationalError> {
+        let computed_order;
+        let eval_order = match &self.eval_order {
+            Some(eval_order) => eval_order,
+            None => {
+                computed_order = self
+                    .data
+                    .eval_order()
+                    .ok_or(NotCombinationalError::HasCycles)?;
+                &computed_order
+            }
+        };
+
+        self.settled = false;
+        if let SimulationRunResult::Err(err) = self.data.run_ordered(eval_order, None) {
+            return Err(NotCombinationalError::Conflict(err));
+        }
+
+        self.data.component_update_queue.clear();
+        self.data
+            .component_update_queue
+            .extend(self.data.components.ids());
+        match self.update_components() {
+            SimulationStepResult::Unchanged => {
+                self.settled = true;
+                Ok(())
+            }
+            SimulationStepResult::Changed => Err(NotCombinationalError::Unsettled),
+            SimulationStepResult::Err(err) => Err(NotCombinationalError::Conflict(err)),
+        }
+    }
+
+    /// Runs the simulation like [Simulator::run_sim], reporting progress every `every_n_steps` steps
+    ///
+    /// `callback` can abort the run early by returning [ControlFlow::Break], in which case `None` is returned.
+    /// If the simulator was created using [SimulatorBuilder::build_acyclic], the single ordered pass
+    /// is never interrupted and `callback` is not invoked.
+    pub fn run_sim_with_callback(
+        &mut self,
+        max_steps: u64,
+        every_n_steps: NonZeroU64,
+        mut callback: impl FnMut(SimulationProgress) -> ControlFlow<()>,
+    ) -> Option<SimulationRunResult> {
+        if let Some(eval_order) = &self.eval_order {
+            let result = self.data.run_ordered(eval_order, None);
+            self.finish_run(&result, 1);
+            return Some(result);
+        }
+
+        let (result, steps) = self.run_until_settled(max_steps, None, |progress| {
+            if (progress.steps % every_n_steps.get()) == 0 {
+                callback(progress)
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+        let Some(result) = result else {
+            self.settled = false;
+            return None;
+        };
+
+        self.finish_run(&result, steps);
+        Some(result)
+    }
+
+    /// Runs the simulation like [Simulator::run_sim], but does not stop when a driver conflict occurs
+    ///
+    /// Conflicting wires are set to Undefined and the simulation continues.
+    /// All wires that had a conflict at any point during the run are returned alongside the result, which is never `Err`.
+    pub fn run_sim_collect_conflicts(
+        &mut self,
+        max_steps: u64,
+    ) -> (SimulationRunResult, Vec<WireId>) {
+        let mut conflicts = Vec::new();
+
+        let (result, steps) = if let Some(eval_order) = &self.eval_order {
+            (self.data.run_ordered(eval_order, Some(&mut conflicts)), 1)
+        } else {
+            self.settle(max_steps, Some(&mut conflicts))
+        };
+
+        conflicts.sort_unstable();
+        conflicts.dedup();
+
+        self.finish_run(&result, steps);
+        (result, conflicts)
+    }
+
+    /// Records the outcome of a run in the settled flag and the step histogram
+    fn finish_run(&mut self, result: &SimulationRunResult, steps: u64) {
+        self.settled = matches!(result, SimulationRunResult::Ok);
+
+        if let Some(step_histogram) = &mut self.step_histogram {
+            if !matches!(result, SimulationRunResult::Err(_)) {
+                *step_histogram.entry(steps).or_default() += 1;
+            }
+        }
+    }
+
+    /// Toggles the clock of a domain through a full cycle, letting the simulation settle after each edge
+    ///
+    /// The first non-`Ok` result is returned and the clock is left in its current state
+    pub fn tick(
+        &mut self,
+        domain: &str,
+        max_steps: u64,
+    ) -> Result<SimulationRunResult, UnknownClockDomainError> {
+        let ClockDomain { clock, polarity } = *self
+            .data
+            .clock_domains
+            .get(domain)
+            .ok_or(UnknownClockDomainError)?;
+
+        for state in [polarity.active_state(), polarity.inactive_state()] {
+            self.data
+                .set_wire_drive(clock, &LogicState::from_bool(state))
+                .expect("invalid clock wire");
+
+            match self.run_sim(max_steps) {
+                SimulationRunResult::Ok => {}
+                result => return Ok(result),
             }
         }
+
+        Ok(SimulationRunResult::Ok)
+    }
+
+    /// Starts recording how many steps successive calls to `run_sim` take
+    ///
+    /// Runs that produce an error are not recorded
+    #[inline]
+    pub fn enable_step_histogram(&mut self) {
+        self.step_histogram.get_or_insert_with(BTreeMap::new);
+    }
+
+    /// Gets the number of `run_sim` calls for each step count, if recording is enabled
+    #[inline]
+    pub fn step_histogram(&self) -> Option<&BTreeMap<u64, u64>> {
+        self.step_histogram.as_ref()
+    }
+
+    /// Clears all recorded step counts
+    #[inline]
+    pub fn clear_step_histogram(&mut self) {
+        if let Some(step_histogram) = &mut self.step_histogram {
+            step_histogram.clear();
+        }
     }
 }
 
@@ -901,22 +2953,150 @@ pub enum ClockPolarity {
     Falling = 0,
 }
 
-impl ClockPolarity {
+impl ClockPolarity {
+    #[inline]
+    const fn active_state(self) -> bool {
+        match self {
+            ClockPolarity::Rising => true,
+            ClockPolarity::Falling => false,
+        }
+    }
+
+    #[inline]
+    const fn inactive_state(self) -> bool {
+        match self {
+            ClockPolarity::Rising => false,
+            ClockPolarity::Falling => true,
+        }
+    }
+}
+
+/// Defines the level at which an enable or write input is active
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnablePolarity {
+    /// The input is active when it is at logic 1
+    #[default]
+    ActiveHigh = 1,
+    /// The input is active when it is at logic 0
+    ActiveLow = 0,
+}
+
+impl EnablePolarity {
+    #[inline]
+    const fn active_state(self) -> bool {
+        match self {
+            EnablePolarity::ActiveHigh => true,
+            EnablePolarity::ActiveLow => false,
+        }
+    }
+}
+
+/// Defines which edges of a signal an edge detector reacts to
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// The signal changing from low to high
+    #[default]
+    Rising,
+    /// The signal changing from high to low
+    Falling,
+    /// Any change between low and high
+    Either,
+}
+
+/// Defines the direction in which bits are rotated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RotateDirection {
+    /// Bits move towards the most significant position
+    Left,
+    /// Bits move towards the least significant position
+    Right,
+}
+
+/// Defines how logic states are formatted in human-readable output
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueFormat {
+    /// Individual bits, including high impedance and undefined ones
+    #[default]
+    Binary,
+    /// An unsigned decimal number
+    Unsigned,
+    /// A signed two's complement decimal number
+    Signed,
+}
+
+impl ValueFormat {
+    fn format(self, state: LogicStateRef) -> String {
+        match self {
+            ValueFormat::Binary => state.to_string(),
+            ValueFormat::Unsigned => state.display_unsigned(),
+            ValueFormat::Signed => state.display_signed(),
+        }
+    }
+}
+
+/// The default maximum number of entries of a memory component, see [SimulatorBuilder::set_max_memory_len]
+pub const DEFAULT_MAX_MEMORY_LEN: usize = 1 << 24;
+
+const STATE_IMAGE_MAGIC: [u8; 4] = *b"GSMI";
+const STATE_IMAGE_VERSION: u32 = 1;
+
+/// The contents of all memory components of a simulator at one point in time
+#[derive(Debug, Clone)]
+pub struct MemoryCheckpoint {
+    contents: HashMap<ComponentId, Vec<InlineLogicState>>,
+}
+
+/// A wire whose state differs between two simulators
+#[derive(Debug)]
+pub struct WireDiff {
+    /// The ID of the wire, which is the same in both simulators
+    pub id: WireId,
+    /// The state of the wire in the simulator [Simulator::diff] was called on
+    pub state: LogicState,
+    /// The state of the wire in the other simulator
+    pub other_state: LogicState,
+}
+
+/// A memory component whose contents differ between two simulators
+#[derive(Debug)]
+pub struct MemoryDiff {
+    /// The ID of the component, which is the same in both simulators
+    pub id: ComponentId,
+    /// The contents of the memory in the simulator [Simulator::diff] was called on
+    pub contents: Vec<LogicState>,
+    /// The contents of the memory in the other simulator
+    pub other_contents: Vec<LogicState>,
+}
+
+/// The differences between the states of two simulators built from the same netlist
+#[derive(Debug, Default)]
+pub struct StateDiff {
+    /// Wires whose state differs
+    pub wires: Vec<WireDiff>,
+    /// Memory components whose contents differ
+    pub memories: Vec<MemoryDiff>,
+}
+
+impl StateDiff {
+    /// Checks whether the two simulators were in the same state
     #[inline]
-    const fn active_state(self) -> bool {
-        match self {
-            ClockPolarity::Rising => true,
-            ClockPolarity::Falling => false,
-        }
+    pub fn is_empty(&self) -> bool {
+        self.wires.is_empty() && self.memories.is_empty()
     }
+}
 
-    #[inline]
-    const fn inactive_state(self) -> bool {
-        match self {
-            ClockPolarity::Rising => false,
-            ClockPolarity::Falling => true,
-        }
+/// Converts a value into a ROM entry, which must have the same width as the data wire
+fn rom_entry(
+    value: &LogicState,
+    data_width: BitWidth,
+) -> Result<InlineLogicState, AddComponentError> {
+    if value.bit_width() != data_width {
+        return Err(AddComponentError::WireWidthMismatch);
     }
+
+    let mut entry = InlineLogicState::undefined(data_width);
+    let _ = entry.copy_from(value);
+    Ok(entry)
 }
 
 /// Builds a simulator
@@ -938,6 +3118,49 @@ impl Default for SimulatorBuilder {
 }
 
 impl SimulatorBuilder {
+    /// Creates a new builder with room for the given number of wires and components
+    ///
+    /// See [SimulatorBuilder::reserve]
+    #[inline]
+    pub fn with_capacity(wires: usize, components: usize) -> Self {
+        let mut builder = Self::default();
+        builder.reserve(wires, components);
+        builder
+    }
+
+    /// Reserves room for at least the given number of additional wires and components
+    ///
+    /// Wire and output states are sized assuming a width of at most 32 bits.
+    /// Components themselves are stored by kind, use [SimulatorBuilder::reserve_components] to reserve room for them.
+    #[inline]
+    pub fn reserve(&mut self, wires: usize, components: usize) {
+        self.data.reserve(wires, components);
+    }
+
+    /// Reserves room for at least the given number of additional components of one kind
+    #[inline]
+    pub fn reserve_components(&mut self, kind: ComponentKind, additional: usize) {
+        self.data.components.reserve(kind, additional);
+    }
+
+    /// Sets the maximum number of entries a memory component may have
+    ///
+    /// Adding a `RAM` or `ROM` with more entries fails with [AddComponentError::MemoryTooLarge]
+    /// before anything is allocated for it, so a malformed netlist cannot exhaust the memory.
+    /// Defaults to [DEFAULT_MAX_MEMORY_LEN].
+    #[inline]
+    pub fn set_max_memory_len(&mut self, max_memory_len: usize) {
+        self.data.max_memory_len = max_memory_len;
+    }
+
+    /// Checks that a memory with the given address width stays within the maximum number of entries
+    fn check_memory_len(&self, addr_width: BitWidth) -> Result<usize, AddComponentError> {
+        1usize
+            .checked_shl(addr_width.get())
+            .filter(|&len| len <= self.data.max_memory_len)
+            .ok_or(AddComponentError::MemoryTooLarge)
+    }
+
     /// Iterates over all wire IDs in the graph
     #[inline]
     pub fn iter_wire_ids(&self) -> impl Iterator<Item = WireId> + '_ {
@@ -950,15 +3173,46 @@ impl SimulatorBuilder {
         self.data.iter_component_ids()
     }
 
+    /// Iterates over all wires in the graph together with their names and widths
+    #[inline]
+    pub fn iter_wires(&self) -> impl Iterator<Item = WireInfo<'_>> + '_ {
+        self.data.iter_wires()
+    }
+
+    /// Iterates over all components in the graph together with their kinds and names
+    #[inline]
+    pub fn iter_components(&self) -> impl Iterator<Item = ComponentInfo<'_>> + '_ {
+        self.data.iter_components()
+    }
+
+    /// The number of wires in the graph
+    #[inline]
+    pub fn wire_count(&self) -> usize {
+        self.data.wire_count()
+    }
+
+    /// The number of components in the graph
+    #[inline]
+    pub fn component_count(&self) -> usize {
+        self.data.component_count()
+    }
+
+    /// Checks whether the graph contains neither wires nor components
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        (self.wire_count() == 0) && (self.component_count() == 0)
+    }
+
     /// Drives a wire to a certain state without needing a component
     ///
-    /// Any unspecified bits will be set to Z
+    /// The new drive must have the same width as the wire.
+    /// Wires driven by a component can only be released to high impedance.
     #[inline]
     pub fn set_wire_drive<'a>(
         &mut self,
         wire: WireId,
         new_drive: impl IntoLogicStateRef<'a>,
-    ) -> Result<(), InvalidWireIdError> {
+    ) -> Result<(), SetWireDriveError> {
         self.data.set_wire_drive(wire, new_drive)
     }
 
@@ -1023,6 +3277,112 @@ impl SimulatorBuilder {
         self.data.get_component_name(component)
     }
 
+    /// Assigns metadata to a component under the given key, replacing any previous value for that key
+    ///
+    /// Metadata is not interpreted by the simulator, it lets tools attach their own information,
+    /// e.g. the source location a component was created from. It is kept by [SimulatorBuilder::build].
+    #[inline]
+    pub fn set_component_metadata<K: Into<Arc<str>>, V: Into<Arc<str>>>(
+        &mut self,
+        component: ComponentId,
+        key: K,
+        value: V,
+    ) -> Result<(), InvalidComponentIdError> {
+        self.data.set_component_metadata(component, key, value)
+    }
+
+    /// Gets the metadata stored under `key` for a component, if any has been assigned
+    #[inline]
+    pub fn get_component_metadata(
+        &self,
+        component: ComponentId,
+        key: &str,
+    ) -> Result<Option<&str>, InvalidComponentIdError> {
+        self.data.get_component_metadata(component, key)
+    }
+
+    /// Iterates over all metadata assigned to a component as key-value pairs, in no particular order
+    #[inline]
+    pub fn iter_component_metadata(
+        &self,
+        component: ComponentId,
+    ) -> Result<impl Iterator<Item = (&str, &str)> + '_, InvalidComponentIdError> {
+        self.data.iter_component_metadata(component)
+    }
+
+    /// Marks a wire as a primary input of the circuit under the given name
+    ///
+    /// Inputs are meant to be driven from outside using [Simulator::set_wire_drive].
+    /// Marked ports are recorded like the connections of an imported module, see [Simulator::ports].
+    pub fn mark_input<S: Into<Arc<str>>>(
+        &mut self,
+        wire: WireId,
+        name: S,
+    ) -> Result<(), InvalidWireIdError> {
+        self.data.wires.get(wire).ok_or(InvalidWireIdError)?;
+        Arc::make_mut(&mut self.data.ports)
+            .inputs
+            .insert(name.into(), wire);
+        Ok(())
+    }
+
+    /// Marks a wire as a primary output of the circuit under the given name
+    ///
+    /// Marked ports are recorded like the connections of an imported module, see [Simulator::ports].
+    pub fn mark_output<S: Into<Arc<str>>>(
+        &mut self,
+        wire: WireId,
+        name: S,
+    ) -> Result<(), InvalidWireIdError> {
+        self.data.wires.get(wire).ok_or(InvalidWireIdError)?;
+        Arc::make_mut(&mut self.data.ports)
+            .outputs
+            .insert(name.into(), wire);
+        Ok(())
+    }
+
+    /// The wires marked as primary inputs and outputs so far
+    #[inline]
+    pub fn ports(&self) -> &import::ModuleConnections {
+        &self.data.ports
+    }
+
+    /// Defines a named clock domain that can be driven using [Simulator::tick]
+    ///
+    /// The clock wire is driven to its inactive state
+    pub fn define_clock_domain<S: Into<Arc<str>>>(
+        &mut self,
+        name: S,
+        clock: WireId,
+        polarity: ClockPolarity,
+    ) -> Result<(), DefineClockDomainError> {
+        let wire = self.data.wires.get(clock).ok_or(InvalidWireIdError)?;
+        if wire.bit_width() != BitWidth::MIN {
+            return Err(DefineClockDomainError::WireWidthIncompatible);
+        }
+
+        let name = name.into();
+        if self.data.clock_domains.contains_key(&name) {
+            return Err(DefineClockDomainError::DuplicateName);
+        }
+
+        self.data
+            .set_wire_drive(clock, &LogicState::from_bool(polarity.inactive_state()))
+            .expect("invalid clock wire");
+        Arc::make_mut(&mut self.data.clock_domains).insert(name, ClockDomain { clock, polarity });
+
+        Ok(())
+    }
+
+    /// Lists all wires that are driven by more than one component output, together with the driving components
+    ///
+    /// Such wires only report a conflict once their drivers actually disagree during simulation,
+    /// so this catches bus contention before running anything. Tied wires count as a single wire.
+    /// Buses that are intentionally shared, e.g. between several buffers, are listed as well.
+    pub fn find_multiply_driven_wires(&self) -> Vec<(WireId, SmallVec<[ComponentId; 2]>)> {
+        self.data.find_multiply_driven_wires()
+    }
+
     /// Collects statistics of the simulation
     #[inline]
     pub fn stats(&self) -> SimulationStats {
@@ -1241,7 +3601,18 @@ impl SimulatorBuilder {
     pub fn add_wire(&mut self, bit_width: BitWidth) -> Option<WireId> {
         let state_id = self.data.wire_states.alloc(bit_width).ok()?;
         let wire = Wire::new(bit_width, state_id);
-        self.data.wires.push(wire)
+        Arc::make_mut(&mut self.data.wires).push(wire)
+    }
+
+    /// Ties two wires of the same width together, so they always share the same state
+    ///
+    /// Tied wires behave exactly like a single wire, no component is added to the simulation.
+    /// Components connected to either wire read the same state, and the drivers of both wires are combined,
+    /// so if both wires are driven with different values this is reported as a conflict on the tied wire
+    /// with the lowest ID. A drive set on either wire applies to both; if both wires already had a drive,
+    /// [AddComponentError::ConflictingDrives] is returned and the wires are not tied.
+    pub fn tie_wires(&mut self, a: WireId, b: WireId) -> Result<(), AddComponentError> {
+        self.data.tie_wires(a, b)
     }
 
     #[inline]
@@ -1249,13 +3620,34 @@ impl SimulatorBuilder {
         &mut self,
         args: T::Args<'_>,
     ) -> Result<ComponentId, AddComponentError> {
-        let component = T::new(args, &mut self.data.wires, &mut self.data.output_states)?;
-        if let Some(id) = self.data.components.push(component) {
-            args.connect_drivers(id, &mut self.data.wires)?;
-            Ok(id)
-        } else {
-            Err(AddComponentError::TooManyComponents)
+        // Creating the component already connects its outputs, so the limit has to be checked first
+        if self.data.components.remaining_capacity(T::KIND) == 0 {
+            return Err(AddComponentError::ComponentLimitReached {
+                kind: T::KIND,
+                count: self.data.components.kind_count(T::KIND),
+            });
         }
+
+        let component = T::new(
+            args,
+            Arc::make_mut(&mut self.data.wires),
+            &mut self.data.output_states,
+        )?;
+        let id = self
+            .data
+            .components
+            .push(component)
+            .expect("component capacity was checked");
+        args.connect_drivers(id, Arc::make_mut(&mut self.data.wires))?;
+        Ok(id)
+    }
+
+    /// The number of components of the given kind that can still be added
+    ///
+    /// Every kind of component has its own limit, so one kind can run out while others still have room.
+    #[inline]
+    pub fn remaining_component_capacity(&self, kind: ComponentKind) -> usize {
+        self.data.components.remaining_capacity(kind)
     }
 
     /// Adds an `AND Gate` component to the simulation
@@ -1265,6 +3657,7 @@ impl SimulatorBuilder {
         output: WireId,
     ) -> Result<ComponentId, AddComponentError> {
         match inputs {
+            [] | [_] => Err(AddComponentError::TooFewInputs),
             &[input_a, input_b] => self.add_component::<AndGate>(BinaryGateArgs {
                 input_a,
                 input_b,
@@ -1281,6 +3674,7 @@ impl SimulatorBuilder {
         output: WireId,
     ) -> Result<ComponentId, AddComponentError> {
         match inputs {
+            [] | [_] => Err(AddComponentError::TooFewInputs),
             &[input_a, input_b] => self.add_component::<OrGate>(BinaryGateArgs {
                 input_a,
                 input_b,
@@ -1297,6 +3691,7 @@ impl SimulatorBuilder {
         output: WireId,
     ) -> Result<ComponentId, AddComponentError> {
         match inputs {
+            [] | [_] => Err(AddComponentError::TooFewInputs),
             &[input_a, input_b] => self.add_component::<XorGate>(BinaryGateArgs {
                 input_a,
                 input_b,
@@ -1306,74 +3701,328 @@ impl SimulatorBuilder {
         }
     }
 
-    /// Adds a `NAND Gate` component to the simulation
-    pub fn add_nand_gate(
+    /// Adds a `NAND Gate` component to the simulation
+    pub fn add_nand_gate(
+        &mut self,
+        inputs: &[WireId],
+        output: WireId,
+    ) -> Result<ComponentId, AddComponentError> {
+        match inputs {
+            [] | [_] => Err(AddComponentError::TooFewInputs),
+            &[input_a, input_b] => self.add_component::<NandGate>(BinaryGateArgs {
+                input_a,
+                input_b,
+                output,
+            }),
+            _ => self.add_component::<WideNandGate>(WideGateArgs { inputs, output }),
+        }
+    }
+
+    /// Adds a `NOR Gate` component to the simulation
+    pub fn add_nor_gate(
+        &mut self,
+        inputs: &[WireId],
+        output: WireId,
+    ) -> Result<ComponentId, AddComponentError> {
+        match inputs {
+            [] | [_] => Err(AddComponentError::TooFewInputs),
+            &[input_a, input_b] => self.add_component::<NorGate>(BinaryGateArgs {
+                input_a,
+                input_b,
+                output,
+            }),
+            _ => self.add_component::<WideNorGate>(WideGateArgs { inputs, output }),
+        }
+    }
+
+    /// Adds an `XNOR Gate` component to the simulation
+    pub fn add_xnor_gate(
+        &mut self,
+        inputs: &[WireId],
+        output: WireId,
+    ) -> Result<ComponentId, AddComponentError> {
+        match inputs {
+            [] | [_] => Err(AddComponentError::TooFewInputs),
+            &[input_a, input_b] => self.add_component::<XnorGate>(BinaryGateArgs {
+                input_a,
+                input_b,
+                output,
+            }),
+            _ => self.add_component::<WideXnorGate>(WideGateArgs { inputs, output }),
+        }
+    }
+
+    /// Adds a `NOT Gate` component to the simulation
+    pub fn add_not_gate(
+        &mut self,
+        input: WireId,
+        output: WireId,
+    ) -> Result<ComponentId, AddComponentError> {
+        self.add_component::<NotGate>(UnaryGateArgs { input, output })
+    }
+
+    /// Adds a `Buffer` component to the simulation
+    pub fn add_buffer(
+        &mut self,
+        input: WireId,
+        enable: WireId,
+        output: WireId,
+    ) -> Result<ComponentId, AddComponentError> {
+        self.add_component::<Buffer>(BufferArgs {
+            input,
+            enable,
+            output,
+            disabled_value: LogicBitState::HighZ,
+        })
+    }
+
+    /// Adds a `Buffer` component to the simulation that drives `disabled_value` instead of high impedance while disabled
+    pub fn add_gated_buffer(
+        &mut self,
+        input: WireId,
+        enable: WireId,
+        output: WireId,
+        disabled_value: LogicBitState,
+    ) -> AddComponentResult {
+        self.add_component::<Buffer>(BufferArgs {
+            input,
+            enable,
+            output,
+            disabled_value,
+        })
+    }
+
+    /// Adds an `Edge Detector` component to the simulation
+    ///
+    /// `input` is sampled on every clock edge, and `output` is high for one clock cycle
+    /// whenever the sampled value changed in the direction selected by `edge`.
+    pub fn add_edge_detector(
+        &mut self,
+        input: WireId,
+        output: WireId,
+        clock: WireId,
+        clock_polarity: ClockPolarity,
+        edge: EdgeKind,
+    ) -> AddComponentResult {
+        self.add_component::<EdgeDetector>(EdgeDetectorArgs {
+            input,
+            output,
+            clock,
+            clock_polarity,
+            edge,
+        })
+    }
+
+    /// Adds a `Delay` component to the simulation
+    ///
+    /// `input` is captured on every clock edge and shown on `output` until the next one,
+    /// so `output` lags `input` by exactly one clock cycle.
+    pub fn add_delay(
+        &mut self,
+        input: WireId,
+        output: WireId,
+        clock: WireId,
+        clock_polarity: ClockPolarity,
+    ) -> AddComponentResult {
+        self.add_component::<Delay>(DelayArgs {
+            input,
+            output,
+            clock,
+            clock_polarity,
+        })
+    }
+
+    /// Adds a `Register` component to the simulation
+    ///
+    /// On every clock edge `data_in` is stored if `enable` is high. `data_out` always shows the stored value.
+    pub fn add_register(
+        &mut self,
+        data_in: WireId,
+        data_out: WireId,
+        enable: WireId,
+        clock: WireId,
+        clock_polarity: ClockPolarity,
+    ) -> AddComponentResult {
+        self.add_register_with_enable_polarity(
+            data_in,
+            data_out,
+            enable,
+            clock,
+            clock_polarity,
+            EnablePolarity::ActiveHigh,
+        )
+    }
+
+    /// Adds a `Register` component to the simulation, whose `enable` input is active at the given level
+    pub fn add_register_with_enable_polarity(
+        &mut self,
+        data_in: WireId,
+        data_out: WireId,
+        enable: WireId,
+        clock: WireId,
+        clock_polarity: ClockPolarity,
+        enable_polarity: EnablePolarity,
+    ) -> AddComponentResult {
+        self.add_component::<Register>(RegisterArgs {
+            data_in,
+            data_out,
+            enable,
+            enable_polarity,
+            clock,
+            clock_polarity,
+        })
+    }
+
+    /// Adds a `FIFO` component to the simulation
+    ///
+    /// On every clock edge the oldest value is removed if `pop` is high, then `data_in` is appended
+    /// if `push` is high and there is space left. `data_out` always shows the oldest value.
+    pub fn add_fifo(
+        &mut self,
+        data_in: WireId,
+        data_out: WireId,
+        push: WireId,
+        pop: WireId,
+        clock: WireId,
+        full: WireId,
+        empty: WireId,
+        depth: NonZeroUsize,
+        clock_polarity: ClockPolarity,
+    ) -> AddComponentResult {
+        self.add_component::<Fifo>(FifoArgs {
+            data_in,
+            data_out,
+            push,
+            pop,
+            clock,
+            full,
+            empty,
+            depth,
+            clock_polarity,
+        })
+    }
+
+    /// Adds a `RAM` component to the simulation
+    ///
+    /// On every clock edge `data_in` is written to the entry at `write_addr` if `write` is high.
+    /// `data_out` always shows the entry at `read_addr`, or an undefined value if the address is not fully known.
+    pub fn add_ram(
         &mut self,
-        inputs: &[WireId],
-        output: WireId,
-    ) -> Result<ComponentId, AddComponentError> {
-        match inputs {
-            &[input_a, input_b] => self.add_component::<NandGate>(BinaryGateArgs {
-                input_a,
-                input_b,
-                output,
-            }),
-            _ => self.add_component::<WideNandGate>(WideGateArgs { inputs, output }),
-        }
+        write_addr: WireId,
+        data_in: WireId,
+        read_addr: WireId,
+        data_out: WireId,
+        write: WireId,
+        clock: WireId,
+        clock_polarity: ClockPolarity,
+    ) -> AddComponentResult {
+        self.add_ram_with_write_polarity(
+            write_addr,
+            data_in,
+            read_addr,
+            data_out,
+            write,
+            clock,
+            clock_polarity,
+            EnablePolarity::ActiveHigh,
+        )
     }
 
-    /// Adds a `NOR Gate` component to the simulation
-    pub fn add_nor_gate(
+    /// Adds a `RAM` component to the simulation, whose `write` input is active at the given level
+    pub fn add_ram_with_write_polarity(
         &mut self,
-        inputs: &[WireId],
-        output: WireId,
-    ) -> Result<ComponentId, AddComponentError> {
-        match inputs {
-            &[input_a, input_b] => self.add_component::<NorGate>(BinaryGateArgs {
-                input_a,
-                input_b,
-                output,
-            }),
-            _ => self.add_component::<WideNorGate>(WideGateArgs { inputs, output }),
-        }
+        write_addr: WireId,
+        data_in: WireId,
+        read_addr: WireId,
+        data_out: WireId,
+        write: WireId,
+        clock: WireId,
+        clock_polarity: ClockPolarity,
+        write_polarity: EnablePolarity,
+    ) -> AddComponentResult {
+        let addr_width = self
+            .data
+            .wires
+            .get(write_addr)
+            .ok_or(AddComponentError::InvalidWireId)?
+            .bit_width();
+        self.check_memory_len(addr_width)?;
+
+        self.add_component::<Ram>(RamArgs {
+            write_addr,
+            data_in,
+            read_addr,
+            data_out,
+            write,
+            write_polarity,
+            clock,
+            clock_polarity,
+        })
     }
 
-    /// Adds an `XNOR Gate` component to the simulation
-    pub fn add_xnor_gate(
+    /// Adds a `ROM` component to the simulation
+    ///
+    /// `data` shows the entry of `contents` at `addr`. Addresses past the end of `contents`, as well as
+    /// addresses that are not fully known, read as undefined. Every entry must have the same width as `data`.
+    pub fn add_rom(
         &mut self,
-        inputs: &[WireId],
-        output: WireId,
-    ) -> Result<ComponentId, AddComponentError> {
-        match inputs {
-            &[input_a, input_b] => self.add_component::<XnorGate>(BinaryGateArgs {
-                input_a,
-                input_b,
-                output,
-            }),
-            _ => self.add_component::<WideXnorGate>(WideGateArgs { inputs, output }),
+        addr: WireId,
+        data: WireId,
+        contents: &[LogicState],
+    ) -> AddComponentResult {
+        if contents.len() > self.data.max_memory_len {
+            return Err(AddComponentError::MemoryTooLarge);
         }
-    }
 
-    /// Adds a `NOT Gate` component to the simulation
-    pub fn add_not_gate(
-        &mut self,
-        input: WireId,
-        output: WireId,
-    ) -> Result<ComponentId, AddComponentError> {
-        self.add_component::<NotGate>(UnaryGateArgs { input, output })
+        let data_width = self
+            .data
+            .wires
+            .get(data)
+            .ok_or(AddComponentError::InvalidWireId)?
+            .bit_width();
+        let contents = contents
+            .iter()
+            .map(|value| rom_entry(value, data_width))
+            .collect::<Result<_, _>>()?;
+
+        self.add_component::<Rom>(RomArgs {
+            addr,
+            data,
+            contents: &contents,
+        })
     }
 
-    /// Adds a `Buffer` component to the simulation
-    pub fn add_buffer(
+    /// Adds a `ROM` component to the simulation, with its contents generated from the address
+    ///
+    /// `init` is called once for each of the `2^addr_width` entries, so no table has to be built up front.
+    /// Every value it returns must have the same width as `data`.
+    pub fn add_rom_with(
         &mut self,
-        input: WireId,
-        enable: WireId,
-        output: WireId,
-    ) -> Result<ComponentId, AddComponentError> {
-        self.add_component::<Buffer>(BinaryGateArgs {
-            input_a: input,
-            input_b: enable,
-            output,
+        addr: WireId,
+        data: WireId,
+        mut init: impl FnMut(usize) -> LogicState,
+    ) -> AddComponentResult {
+        let addr_width = self
+            .data
+            .wires
+            .get(addr)
+            .ok_or(AddComponentError::InvalidWireId)?
+            .bit_width();
+        let data_width = self
+            .data
+            .wires
+            .get(data)
+            .ok_or(AddComponentError::InvalidWireId)?
+            .bit_width();
+        let len = self.check_memory_len(addr_width)?;
+        let contents = (0..len)
+            .map(|index| rom_entry(&init(index), data_width))
+            .collect::<Result<_, _>>()?;
+
+        self.add_component::<Rom>(RomArgs {
+            addr,
+            data,
+            contents: &contents,
         })
     }
 
@@ -1428,6 +4077,155 @@ impl SimulatorBuilder {
         })
     }
 
+    /// Adds an unsigned multiplier to the simulation, producing the low and high half of the full product
+    ///
+    /// All wires must have the same width, the product is twice as wide and split across `low` and `high`.
+    pub fn add_mul_split(
+        &mut self,
+        input_a: WireId,
+        input_b: WireId,
+        low: WireId,
+        high: WireId,
+    ) -> AddComponentResult {
+        self.add_component::<MulSplit>(MulSplitArgs {
+            input_a,
+            input_b,
+            low,
+            high,
+            signed: false,
+        })
+    }
+
+    /// Adds a signed multiplier to the simulation, producing the low and high half of the full product
+    ///
+    /// All wires must have the same width, the product is twice as wide and split across `low` and `high`.
+    pub fn add_mul_split_signed(
+        &mut self,
+        input_a: WireId,
+        input_b: WireId,
+        low: WireId,
+        high: WireId,
+    ) -> AddComponentResult {
+        self.add_component::<MulSplit>(MulSplitArgs {
+            input_a,
+            input_b,
+            low,
+            high,
+            signed: true,
+        })
+    }
+
+    /// Adds an unsigned magnitude comparator to the simulation
+    ///
+    /// `input_a` and `input_b` must have the same width and are compared using a single subtraction.
+    /// The 1 bit wide outputs `less`, `equal` and `greater` are set according to how `input_a` relates to `input_b`.
+    pub fn add_magnitude_compare(
+        &mut self,
+        input_a: WireId,
+        input_b: WireId,
+        less: WireId,
+        equal: WireId,
+        greater: WireId,
+    ) -> AddComponentResult {
+        self.add_component::<MagnitudeCompare>(MagnitudeCompareArgs {
+            input_a,
+            input_b,
+            less,
+            equal,
+            greater,
+            signed: false,
+        })
+    }
+
+    /// Adds a signed magnitude comparator to the simulation
+    ///
+    /// `input_a` and `input_b` must have the same width and are compared using a single subtraction.
+    /// The 1 bit wide outputs `less`, `equal` and `greater` are set according to how `input_a` relates to `input_b`.
+    pub fn add_magnitude_compare_signed(
+        &mut self,
+        input_a: WireId,
+        input_b: WireId,
+        less: WireId,
+        equal: WireId,
+        greater: WireId,
+    ) -> AddComponentResult {
+        self.add_component::<MagnitudeCompare>(MagnitudeCompareArgs {
+            input_a,
+            input_b,
+            less,
+            equal,
+            greater,
+            signed: true,
+        })
+    }
+
+    /// Adds a `Rotate Through Carry` component to the simulation
+    ///
+    /// `input` and `carry_in` are rotated by `amount` as a single ring of `width + 1` bits,
+    /// with the carry directly above the most significant bit. The rotated value is written
+    /// to `output` and the bit that ends up in the carry position to `carry_out`.
+    /// `amount` is unsigned and may have any width, rotating by `width + 1` is a full cycle.
+    pub fn add_rotate_through_carry(
+        &mut self,
+        input: WireId,
+        amount: WireId,
+        carry_in: WireId,
+        output: WireId,
+        carry_out: WireId,
+        direction: RotateDirection,
+    ) -> AddComponentResult {
+        self.add_component::<RotateThroughCarry>(RotateThroughCarryArgs {
+            input,
+            amount,
+            carry_in,
+            output,
+            carry_out,
+            direction,
+        })
+    }
+
+    /// Adds a `Slice` component to the simulation
+    ///
+    /// `output` receives the bits of `input` starting at bit `offset`.
+    pub fn add_slice(&mut self, input: WireId, offset: u8, output: WireId) -> AddComponentResult {
+        self.add_component::<Slice>(SliceArgs {
+            input,
+            offset,
+            output,
+        })
+    }
+
+    /// Adds a new wire carrying the bits of `wire` in `range`
+    ///
+    /// Wire states are allocated in whole words, so a bit range cannot share the state of its wire.
+    /// Instead the new wire is driven by an automatically inserted `Slice` component,
+    /// which makes it usable as an input to any component.
+    pub fn connect_bits(
+        &mut self,
+        wire: WireId,
+        range: std::ops::Range<u32>,
+    ) -> Result<WireId, AddComponentError> {
+        let offset = u8::try_from(range.start).map_err(|_| AddComponentError::OffsetOutOfRange)?;
+        let bit_width = BitWidth::new(range.end.saturating_sub(range.start))
+            .ok_or(AddComponentError::WireWidthIncompatible)?;
+
+        let input_width = self
+            .data
+            .wires
+            .get(wire)
+            .ok_or(AddComponentError::InvalidWireId)?
+            .bit_width();
+        if range.end > input_width.get() {
+            return Err(AddComponentError::OffsetOutOfRange);
+        }
+
+        let output = self
+            .add_wire(bit_width)
+            .ok_or(AddComponentError::TooManyComponents)?;
+        self.add_slice(wire, offset, output)?;
+        Ok(output)
+    }
+
     /*
     def_add_shifter!(
         /// Adds a `Left Shift` component to the simulation
@@ -1447,45 +4245,6 @@ impl SimulatorBuilder {
         ArithmeticRightShift
     );
 
-    /// Adds a `Slice` component to the simulation
-    pub fn add_slice(&mut self, input: WireId, offset: u8, output: WireId) -> AddComponentResult {
-        let input_width = self.get_wire_width(input)?;
-        let output_width = self.get_wire_width(output)?;
-
-        if output_width > input_width {
-            return Err(AddComponentError::WireWidthIncompatible);
-        }
-
-        if ((offset as usize) + (output_width.get() as usize)) > (input_width.get() as usize) {
-            return Err(AddComponentError::OffsetOutOfRange);
-        }
-
-        let output_state = self
-            .data
-            .output_states
-            .push(output_width)
-            .ok_or(AddComponentError::TooManyComponents)?;
-
-        let wire = &self
-            .data
-            .wires
-            .get(input)
-            .ok_or(AddComponentError::InvalidWireId)?;
-        let gate = SmallComponent::new(
-            SmallComponentKind::Slice {
-                input: wire.state,
-                offset,
-            },
-            output,
-        );
-        let id = self.add_small_component(gate, &[output_state])?;
-
-        self.mark_driving(input, id)?;
-        self.mark_driver(output, output_state)?;
-
-        Ok(id)
-    }
-
     /// Adds a `Merge` component to the simulation
     pub fn add_merge(&mut self, inputs: &[WireId], output: WireId) -> AddComponentResult {
         if inputs.is_empty() {
@@ -1986,6 +4745,45 @@ impl SimulatorBuilder {
     }
     */
 
+    /// Copies all wires and components of another circuit into this one
+    ///
+    /// The copied wires keep their drives and names and are not connected to any wire of this circuit,
+    /// the returned map translates their old IDs so they can be connected afterwards.
+    /// Clock domains and ports of `other` are carried over as well, unless this circuit already
+    /// defines one with the same name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the combined circuit exceeds the wire or component limit.
+    pub fn extend_from(&mut self, other: SimulatorBuilder) -> IdRemap {
+        let (wire_map, components) = other.data.copy_subgraph_into(
+            self,
+            other.data.iter_wire_ids(),
+            other.data.iter_component_ids(),
+        );
+        let wires = wire_map.wires;
+
+        let clock_domains = Arc::make_mut(&mut self.data.clock_domains);
+        for (name, domain) in Arc::unwrap_or_clone(other.data.clock_domains) {
+            clock_domains.entry(name).or_insert(ClockDomain {
+                clock: wires[&domain.clock],
+                polarity: domain.polarity,
+            });
+        }
+
+        let ports = Arc::make_mut(&mut self.data.ports);
+        let other_ports = Arc::unwrap_or_clone(other.data.ports);
+        for (name, wire) in other_ports.inputs {
+            ports.inputs.entry(name).or_insert(wires[&wire]);
+        }
+
+        for (name, wire) in other_ports.outputs {
+            ports.outputs.entry(name).or_insert(wires[&wire]);
+        }
+
+        IdRemap { wires, components }
+    }
+
     /// Imports a module into this circuit
     #[inline]
     pub fn import_module<T: import::ModuleImporter>(
@@ -1997,10 +4795,121 @@ impl SimulatorBuilder {
 
     /// Creates the simulator
     #[inline]
-    pub fn build(self) -> Simulator {
+    pub fn build(mut self) -> Simulator {
+        self.data.finish_build();
+        let mut sim = Simulator {
+            data: self.data,
+            vcd: std::io::sink(),
+            eval_order: None,
+            start_order: None,
+            step_histogram: None,
+            settled: false,
+        };
+
+        sim.reset();
+        sim
+    }
+
+    /// Creates the simulator, starting every run with a pass over the circuit in dependency order
+    ///
+    /// Like at the start of any run, every wire and component is updated, but in an order that makes
+    /// an acyclic circuit settle in a single pass instead of taking as many steps as the circuit is deep.
+    /// Unlike [SimulatorBuilder::build_acyclic], circuits containing combinational cycles are accepted
+    /// and simulated exactly like with [SimulatorBuilder::build], as are runs that collect conflicts.
+    /// Computing the order makes building slower, so this pays off for deep circuits that are run many times.
+    pub fn build_with_ordered_start(mut self) -> Simulator {
+        self.data.finish_build();
+        let start_order = self.data.eval_order();
+        if start_order.is_none() {
+            sim_log!(
+                debug,
+                "circuit contains cycles, falling back to unordered start"
+            );
+        }
+
+        let mut sim = Simulator {
+            data: self.data,
+            vcd: std::io::sink(),
+            eval_order: None,
+            start_order,
+            step_histogram: None,
+            settled: false,
+        };
+
+        sim.reset();
+        sim
+    }
+
+    /// Creates a simulator that evaluates the circuit in a single ordered pass
+    ///
+    /// The evaluation order is computed once here, so `run_sim` does not have to iterate until the circuit settles.
+    /// Fails if the circuit contains combinational cycles, in which case [SimulatorBuilder::build] has to be used instead.
+    pub fn build_acyclic(mut self) -> Result<Simulator, HasCyclesError> {
+        self.data.finish_build();
+        let Some(eval_order) = self.data.eval_order() else {
+            return Err(HasCyclesError {
+                builder: Box::new(self),
+            });
+        };
+
         let mut sim = Simulator {
             data: self.data,
             vcd: std::io::sink(),
+            eval_order: Some(eval_order),
+            start_order: None,
+            step_histogram: None,
+            settled: false,
+        };
+
+        sim.reset();
+        Ok(sim)
+    }
+
+    /// Turns the builder into an immutable netlist that can be instantiated any number of times
+    #[inline]
+    pub fn freeze(mut self) -> Arc<FrozenNetlist> {
+        self.data.finish_build();
+        Arc::new(FrozenNetlist { data: self.data })
+    }
+}
+
+/// An immutable circuit that simulators can be instantiated from
+///
+/// The netlist holds the structure of the circuit: wires with their widths and drives, components and
+/// their connections, names and clock domains. It never changes after [SimulatorBuilder::freeze], so it
+/// can be shared between threads. Every simulator created by [FrozenNetlist::instantiate] shares the
+/// wires, names and clock domains of the netlist and only gets its own wire states, output states and
+/// components, which hold their contents (e.g. register values or FIFO entries) inline, so instances
+/// never affect each other.
+///
+/// Wire and component IDs of the original builder are valid for every instance.
+#[allow(missing_debug_implementations)]
+pub struct FrozenNetlist {
+    data: SimulatorData,
+}
+
+impl FrozenNetlist {
+    /// The number of wires in the netlist
+    #[inline]
+    pub fn wire_count(&self) -> usize {
+        self.data.wire_count()
+    }
+
+    /// The number of components in the netlist
+    #[inline]
+    pub fn component_count(&self) -> usize {
+        self.data.component_count()
+    }
+
+    /// Creates a new simulator in its reset state from this netlist
+    pub fn instantiate(&self) -> Simulator {
+        let mut sim = Simulator {
+            data: self.data.clone(),
+            vcd: std::io::sink(),
+            eval_order: None,
+            start_order: None,
+            step_histogram: None,
+            settled: false,
         };
 
         sim.reset();
@@ -2010,6 +4919,7 @@ impl SimulatorBuilder {
 
 assert_impl_all!(SimulatorBuilder: Send);
 assert_impl_all!(Simulator: Send);
+assert_impl_all!(FrozenNetlist: Send, Sync);
 
 //#[cfg(feature = "tracing")]
 //mod tracing;
@@ -298,24 +298,41 @@ impl<T: Id, const N: usize> LogicStateAllocator<T, N> {
         }
     }
 
+    fn grow(&mut self, new_word_cap: u32) -> Result<(), OutOfMemoryError> {
+        unsafe {
+            for bit_planes in &mut self.bit_planes {
+                bit_planes
+                    .bit_plane_0
+                    .realloc(self.word_len, self.word_cap, new_word_cap)?;
+
+                bit_planes
+                    .bit_plane_1
+                    .realloc(self.word_len, self.word_cap, new_word_cap)?;
+            }
+        }
+
+        self.word_cap = new_word_cap;
+        Ok(())
+    }
+
     #[inline]
     fn reserve(&mut self, new_word_len: u32) -> Result<(), OutOfMemoryError> {
         if new_word_len > self.word_cap {
-            let new_word_cap = new_word_len.saturating_mul(2);
+            self.grow(new_word_len.saturating_mul(2))?;
+        }
 
-            unsafe {
-                for bit_planes in &mut self.bit_planes {
-                    bit_planes
-                        .bit_plane_0
-                        .realloc(self.word_len, self.word_cap, new_word_cap)?;
-
-                    bit_planes
-                        .bit_plane_1
-                        .realloc(self.word_len, self.word_cap, new_word_cap)?;
-                }
-            }
+        Ok(())
+    }
+
+    /// Makes room for at least `additional_words` more words without over-allocating
+    pub(crate) fn reserve_exact(&mut self, additional_words: u32) -> Result<(), OutOfMemoryError> {
+        let new_word_len = self
+            .word_len
+            .checked_add(additional_words)
+            .ok_or(OutOfMemoryError)?;
 
-            self.word_cap = new_word_cap;
+        if new_word_len > self.word_cap {
+            self.grow(new_word_len)?;
         }
 
         Ok(())
@@ -359,6 +376,67 @@ impl<T: Id, const N: usize> LogicStateAllocator<T, N> {
             self.bit_planes[0].bit_plane_1.set(self.word_len, u8::MAX);
         }
     }
+
+    /// Feeds the `index`-th set of bit planes into a hasher, e.g. the current states of all wires
+    pub(crate) fn hash_bit_planes<H: std::hash::Hasher>(&self, index: usize, hasher: &mut H) {
+        use std::hash::Hash;
+
+        let bit_planes = &self.bit_planes[index];
+        let (bit_plane_0, bit_plane_1) = unsafe {
+            // SAFETY: the first `word_len` words of every bit plane are initialized
+            (
+                std::slice::from_raw_parts(
+                    bit_planes.bit_plane_0.0.as_ptr(),
+                    self.word_len as usize,
+                ),
+                std::slice::from_raw_parts(
+                    bit_planes.bit_plane_1.0.as_ptr(),
+                    self.word_len as usize,
+                ),
+            )
+        };
+
+        bit_plane_0.hash(hasher);
+        bit_plane_1.hash(hasher);
+    }
+
+    pub(crate) fn clear_all_states(&mut self) {
+        unsafe {
+            // Min/Max coresponds to the high impedance state
+            for bit_planes in &mut self.bit_planes {
+                bit_planes.bit_plane_0.set(self.word_len, u8::MIN);
+                bit_planes.bit_plane_1.set(self.word_len, u8::MAX);
+            }
+        }
+    }
+}
+
+impl<T: Id, const N: usize> Clone for LogicStateAllocator<T, N> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::new();
+        clone.reserve_exact(self.word_len).expect("out of memory");
+
+        unsafe {
+            // SAFETY:
+            //   - The first `word_len` words of every bit plane are initialized.
+            //   - The clone was just allocated with room for `word_len` words, so the buffers don't overlap.
+            for (src, dst) in self.bit_planes.iter().zip(&mut clone.bit_planes) {
+                ptr::copy_nonoverlapping(
+                    src.bit_plane_0.0.as_ptr(),
+                    dst.bit_plane_0.0.as_ptr(),
+                    self.word_len as usize,
+                );
+                ptr::copy_nonoverlapping(
+                    src.bit_plane_1.0.as_ptr(),
+                    dst.bit_plane_1.0.as_ptr(),
+                    self.word_len as usize,
+                );
+            }
+        }
+
+        clone.word_len = self.word_len;
+        clone
+    }
 }
 
 impl<T: Id, const N: usize> Drop for LogicStateAllocator<T, N> {
@@ -12,8 +12,24 @@ pub enum LogicStateFromStrError {
     InvalidBitWidth,
     /// The string contained a character other than `x`, `X`, `z`, `Z`, `0` or `1`
     IllegalCharacter(u8),
+    /// The value does not fit into the requested number of bits
+    ValueTooWide,
 }
 
+impl fmt::Display for LogicStateFromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBitWidth => f.write_str("the number of bits was not between 1 and 256"),
+            Self::IllegalCharacter(c) => write!(f, "illegal character `{}`", c.escape_ascii()),
+            Self::ValueTooWide => {
+                f.write_str("the value does not fit into the requested number of bits")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LogicStateFromStrError {}
+
 /// The logic state of a single bit
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
@@ -320,7 +336,7 @@ impl Iterator for Bits<'_> {
 
 impl std::iter::FusedIterator for Bits<'_> {}
 
-const MAX_WORD_COUNT: usize = BitWidth::MAX.get().div_ceil(u32::BITS) as usize;
+pub(crate) const MAX_WORD_COUNT: usize = BitWidth::MAX.get().div_ceil(u32::BITS) as usize;
 
 const ALL_ZERO: [u32; MAX_WORD_COUNT] = [u32::MIN; MAX_WORD_COUNT];
 const ALL_ONE: [u32; MAX_WORD_COUNT] = [u32::MAX; MAX_WORD_COUNT];
@@ -417,6 +433,143 @@ impl LogicStateRepr {
         let (bit_plane_0, bit_plane_1) = self.bit_planes();
         Bits::new(self.bit_width(), bit_plane_0, bit_plane_1)
     }
+
+    fn matches(&self, expected: &LogicStateRepr) -> bool {
+        let bit_width = self.bit_width();
+        if bit_width != expected.bit_width() {
+            return false;
+        }
+
+        let (actual_plane_0, actual_plane_1) = self.bit_planes();
+        let (expected_plane_0, expected_plane_1) = expected.bit_planes();
+        let word_len = bit_width.word_len() as usize;
+
+        itertools::izip!(
+            actual_plane_0,
+            actual_plane_1,
+            expected_plane_0,
+            expected_plane_1
+        )
+        .enumerate()
+        .all(|(i, (&a0, &a1, &e0, &e1))| {
+            let mask = if i == (word_len - 1) {
+                bit_width.last_word_mask()
+            } else {
+                u32::MAX
+            };
+
+            // Bits that are Z or X in the expected state are don't-cares
+            let care = !e1 & mask;
+            (((a0 ^ e0) | (a1 ^ e1)) & care) == 0
+        })
+    }
+
+    fn eq_width(&self, other: &LogicStateRepr, width: BitWidth) -> bool {
+        if (width > self.bit_width()) || (width > other.bit_width()) {
+            return false;
+        }
+
+        let word_len = width.word_len() as usize;
+        let (a_plane_0, a_plane_1) = self.bit_planes();
+        let (b_plane_0, b_plane_1) = other.bit_planes();
+
+        itertools::izip!(
+            &a_plane_0[..word_len],
+            &a_plane_1[..word_len],
+            &b_plane_0[..word_len],
+            &b_plane_1[..word_len]
+        )
+        .enumerate()
+        .all(|(i, (&a0, &a1, &b0, &b1))| {
+            let mask = if i == (word_len - 1) {
+                width.last_word_mask()
+            } else {
+                u32::MAX
+            };
+
+            (((a0 ^ b0) | (a1 ^ b1)) & mask) == 0
+        })
+    }
+
+    fn to_decimal(self, signed: bool) -> Option<String> {
+        let bit_width = self.bit_width();
+        let word_len = bit_width.word_len() as usize;
+        let (bit_plane_0, bit_plane_1) = self.bit_planes();
+
+        let (&last_word_1, head_words_1) = bit_plane_1.split_last().unwrap();
+        if head_words_1.iter().any(|&word| word != 0)
+            || ((last_word_1 & bit_width.last_word_mask()) != 0)
+        {
+            return None;
+        }
+
+        let mut words = [0; MAX_WORD_COUNT];
+        words[..word_len].copy_from_slice(bit_plane_0);
+        words[word_len - 1] &= bit_width.last_word_mask();
+
+        let sign_bit = (bit_width.get() - 1) % u32::BITS;
+        let negative = signed && (((words[word_len - 1] >> sign_bit) & 0x1) != 0);
+        if negative {
+            // Two's complement negation within the width of the state
+            let mut carry = true;
+            for word in &mut words[..word_len] {
+                (*word, carry) = (!*word).overflowing_add(carry as u32);
+            }
+            words[word_len - 1] &= bit_width.last_word_mask();
+        }
+
+        let mut digits = Vec::new();
+        loop {
+            let mut remainder = 0u64;
+            for word in words[..word_len].iter_mut().rev() {
+                let value = (remainder << u32::BITS) | (*word as u64);
+                *word = (value / 10) as u32;
+                remainder = value % 10;
+            }
+
+            digits.push(b'0' + (remainder as u8));
+            if words[..word_len].iter().all(|&word| word == 0) {
+                break;
+            }
+        }
+
+        if negative {
+            digits.push(b'-');
+        }
+
+        digits.reverse();
+        Some(String::from_utf8(digits).unwrap())
+    }
+
+    fn to_bytes(self, endianness: Endianness) -> Option<Vec<u8>> {
+        let bit_width = self.bit_width();
+        let (bit_plane_0, bit_plane_1) = self.bit_planes();
+
+        let (&last_word_1, head_words_1) = bit_plane_1.split_last().unwrap();
+        if head_words_1.iter().any(|&word| word != 0)
+            || ((last_word_1 & bit_width.last_word_mask()) != 0)
+        {
+            return None;
+        }
+
+        let byte_len = bit_width.get().div_ceil(u8::BITS) as usize;
+        let mut bytes: Vec<u8> = bit_plane_0
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .take(byte_len)
+            .collect();
+
+        let last_byte_width = bit_width.get() % u8::BITS;
+        if last_byte_width > 0 {
+            bytes[byte_len - 1] &= (1u8 << last_byte_width) - 1;
+        }
+
+        if endianness == Endianness::Big {
+            bytes.reverse();
+        }
+
+        Some(bytes)
+    }
 }
 
 impl fmt::Display for LogicStateRepr {
@@ -432,6 +585,16 @@ impl fmt::Display for LogicStateRepr {
     }
 }
 
+/// The order of bytes in a byte slice
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// The least significant byte comes first
+    #[default]
+    Little,
+    /// The most significant byte comes first
+    Big,
+}
+
 /// The state of a group of bits
 #[repr(transparent)]
 pub struct LogicState {
@@ -471,6 +634,66 @@ impl LogicState {
         }
     }
 
+    /// A state of the given width with all bits having the value `0`
+    ///
+    /// Example:
+    /// ```
+    /// use gsim::{bit_width, LogicState};
+    ///
+    /// let state = LogicState::all_zeros(bit_width!(4));
+    /// assert_eq!(state.bit_width(), bit_width!(4));
+    /// assert_eq!(state.to_string(), "0000");
+    /// ```
+    #[inline]
+    pub const fn all_zeros(bit_width: BitWidth) -> Self {
+        Self::logic_0(bit_width)
+    }
+
+    /// A state of the given width with all bits having the value `1`
+    ///
+    /// Example:
+    /// ```
+    /// use gsim::{bit_width, LogicState};
+    ///
+    /// let state = LogicState::all_ones(bit_width!(4));
+    /// assert_eq!(state.bit_width(), bit_width!(4));
+    /// assert_eq!(state.to_string(), "1111");
+    /// ```
+    #[inline]
+    pub const fn all_ones(bit_width: BitWidth) -> Self {
+        Self::logic_1(bit_width)
+    }
+
+    /// A state of the given width with all bits being undefined
+    ///
+    /// Example:
+    /// ```
+    /// use gsim::{bit_width, LogicState};
+    ///
+    /// let state = LogicState::all_undefined(bit_width!(4));
+    /// assert_eq!(state.bit_width(), bit_width!(4));
+    /// assert_eq!(state.to_string(), "XXXX");
+    /// ```
+    #[inline]
+    pub const fn all_undefined(bit_width: BitWidth) -> Self {
+        Self::undefined(bit_width)
+    }
+
+    /// A state of the given width with all bits being in high impedance
+    ///
+    /// Example:
+    /// ```
+    /// use gsim::{bit_width, LogicState};
+    ///
+    /// let state = LogicState::all_high_z(bit_width!(4));
+    /// assert_eq!(state.bit_width(), bit_width!(4));
+    /// assert_eq!(state.to_string(), "ZZZZ");
+    /// ```
+    #[inline]
+    pub const fn all_high_z(bit_width: BitWidth) -> Self {
+        Self::high_z(bit_width)
+    }
+
     /// Creates a state from a single bit
     #[inline]
     pub const fn from_bit(value: LogicBitState) -> Self {
@@ -594,6 +817,38 @@ impl LogicState {
         }
     }
 
+    /// Creates a state representing a number stored as bytes
+    ///
+    /// Bytes past the width of the state are ignored, missing bytes are assigned the value 0
+    pub fn from_bytes(bytes: &[u8], bit_width: BitWidth, endianness: Endianness) -> Self {
+        let byte_len = bit_width.get().div_ceil(u8::BITS) as usize;
+        let word_len = bit_width.word_len() as usize;
+
+        let mut words = [0; MAX_WORD_COUNT];
+        let mut set_byte = |(i, &byte): (usize, &u8)| {
+            let word_index = i / (u32::BITS / u8::BITS) as usize;
+            let bit_index = (i % (u32::BITS / u8::BITS) as usize) * (u8::BITS as usize);
+            words[word_index] |= (byte as u32) << bit_index;
+        };
+
+        match endianness {
+            Endianness::Little => bytes
+                .iter()
+                .take(byte_len)
+                .enumerate()
+                .for_each(&mut set_byte),
+            Endianness::Big => bytes
+                .iter()
+                .rev()
+                .take(byte_len)
+                .enumerate()
+                .for_each(&mut set_byte),
+        }
+        words[word_len - 1] &= bit_width.last_word_mask();
+
+        Self::from_big_int(bit_width, &words[..word_len])
+    }
+
     /// Creates a state with the specified bits (least significant bit first)
     pub fn from_bits(bits: &[LogicBitState]) -> Self {
         let len: u32 = bits.len().try_into().expect("invalid bit width");
@@ -615,6 +870,36 @@ impl LogicState {
         Self::from_bit_planes(bit_width, &bit_plane_0, &bit_plane_1)
     }
 
+    /// Parses a state of the given width from a string with an optional radix prefix
+    ///
+    /// The following formats are accepted:
+    /// - `d` followed by a decimal number, e.g. `d123`
+    /// - `h` followed by a hexadecimal number, e.g. `h1F`
+    /// - `b` followed by a string of bits, e.g. `b10XZ`
+    /// - a string of bits without prefix, e.g. `10XZ`
+    ///
+    /// Strings of bits accept the same characters and use the same bit order as the [FromStr] implementation.
+    /// Values shorter than `bit_width` are padded with `0`, values that do not fit fail to parse.
+    ///
+    /// Example:
+    /// ```
+    /// use gsim::{bit_width, LogicState};
+    ///
+    /// let state = LogicState::parse_prefixed("h1F", bit_width!(8)).unwrap();
+    /// assert_eq!(state, LogicState::from_u32(0x1F, bit_width!(8)));
+    ///
+    /// let state = LogicState::parse_prefixed("d300", bit_width!(8));
+    /// assert!(state.is_err());
+    /// ```
+    pub fn parse_prefixed(s: &str, bit_width: BitWidth) -> Result<Self, LogicStateFromStrError> {
+        match s.as_bytes() {
+            [b'd', digits @ ..] => parse_number(digits, 10, bit_width),
+            [b'h', digits @ ..] => parse_number(digits, 16, bit_width),
+            [b'b', bits @ ..] => parse_bits(bits, bit_width),
+            bits => parse_bits(bits, bit_width),
+        }
+    }
+
     /// The number of bits in this state
     #[inline]
     pub const fn bit_width(&self) -> BitWidth {
@@ -639,6 +924,50 @@ impl LogicState {
         self.repr.bits()
     }
 
+    /// Tests this state against an expected state
+    ///
+    /// Bits that are high impedance or undefined in `expected` are treated as don't-cares and always match
+    #[inline]
+    pub fn matches<'other>(&self, expected: impl IntoLogicStateRef<'other>) -> bool {
+        self.repr.matches(&expected.into_logic_state_ref().repr)
+    }
+
+    /// Tests the first `width` bits of this state and another for equality
+    ///
+    /// Bits past `width` are ignored, unlike `==` which compares the full width of both states.
+    /// Returns `false` if either state is narrower than `width`.
+    #[inline]
+    pub fn eq<'other>(&self, other: impl IntoLogicStateRef<'other>, width: BitWidth) -> bool {
+        self.repr
+            .eq_width(&other.into_logic_state_ref().repr, width)
+    }
+
+    /// Converts the state into bytes
+    ///
+    /// Returns `None` if any bit is high impedance or undefined
+    #[inline]
+    pub fn to_bytes(&self, endianness: Endianness) -> Option<Vec<u8>> {
+        self.repr.to_bytes(endianness)
+    }
+
+    /// Formats the state as an unsigned decimal number
+    ///
+    /// Falls back to the bit representation if any bit is high impedance or undefined
+    pub fn display_unsigned(&self) -> String {
+        self.repr
+            .to_decimal(false)
+            .unwrap_or_else(|| self.repr.to_string())
+    }
+
+    /// Formats the state as a signed two's complement decimal number
+    ///
+    /// Falls back to the bit representation if any bit is high impedance or undefined
+    pub fn display_signed(&self) -> String {
+        self.repr
+            .to_decimal(true)
+            .unwrap_or_else(|| self.repr.to_string())
+    }
+
     /// Turns the logic state into a borrowed form
     #[inline]
     pub const fn borrow(&self) -> LogicStateRef<'_> {
@@ -694,6 +1023,63 @@ fn parse_into(s: &[u8], bit_plane_0: &mut [u32], bit_plane_1: &mut [u32]) {
     }
 }
 
+fn parse_number(
+    digits: &[u8],
+    radix: u32,
+    bit_width: BitWidth,
+) -> Result<LogicState, LogicStateFromStrError> {
+    if digits.is_empty() {
+        return Err(LogicStateFromStrError::InvalidBitWidth);
+    }
+
+    let word_len = bit_width.word_len() as usize;
+    let mut words = [0; MAX_WORD_COUNT];
+    for &c in digits {
+        let digit = (c as char)
+            .to_digit(radix)
+            .ok_or(LogicStateFromStrError::IllegalCharacter(c))?;
+
+        let mut carry = digit as u64;
+        for word in &mut words[..word_len] {
+            let value = (*word as u64) * (radix as u64) + carry;
+            *word = value as u32;
+            carry = value >> u32::BITS;
+        }
+
+        if (carry != 0) || ((words[word_len - 1] & !bit_width.last_word_mask()) != 0) {
+            return Err(LogicStateFromStrError::ValueTooWide);
+        }
+    }
+
+    Ok(LogicState::from_big_int(bit_width, &words[..word_len]))
+}
+
+fn parse_bits(bits: &[u8], bit_width: BitWidth) -> Result<LogicState, LogicStateFromStrError> {
+    if bits.is_empty() {
+        return Err(LogicStateFromStrError::InvalidBitWidth);
+    }
+
+    if let Some(&c) = bits
+        .iter()
+        .find(|&&c| !matches!(c, b'0' | b'1' | b'z' | b'Z' | b'x' | b'X'))
+    {
+        return Err(LogicStateFromStrError::IllegalCharacter(c));
+    }
+
+    if bits.len() > (bit_width.get() as usize) {
+        return Err(LogicStateFromStrError::ValueTooWide);
+    }
+
+    let mut bit_plane_0 = [0; MAX_WORD_COUNT];
+    let mut bit_plane_1 = [0; MAX_WORD_COUNT];
+    parse_into(bits, &mut bit_plane_0, &mut bit_plane_1);
+    Ok(LogicState::from_bit_planes(
+        bit_width,
+        &bit_plane_0,
+        &bit_plane_1,
+    ))
+}
+
 impl FromStr for LogicState {
     type Err = LogicStateFromStrError;
 
@@ -904,6 +1290,7 @@ macro_rules! copy_from_impl {
     };
 }
 
+#[derive(Clone)]
 #[repr(C)]
 pub(crate) struct InlineLogicState {
     bit_width: BitWidth,
@@ -1100,6 +1487,50 @@ impl LogicStateRef<'_> {
         self.repr.bits()
     }
 
+    /// Tests this state against an expected state
+    ///
+    /// Bits that are high impedance or undefined in `expected` are treated as don't-cares and always match
+    #[inline]
+    pub fn matches<'other>(&self, expected: impl IntoLogicStateRef<'other>) -> bool {
+        self.repr.matches(&expected.into_logic_state_ref().repr)
+    }
+
+    /// Tests the first `width` bits of this state and another for equality
+    ///
+    /// Bits past `width` are ignored, unlike `==` which compares the full width of both states.
+    /// Returns `false` if either state is narrower than `width`.
+    #[inline]
+    pub fn eq<'other>(&self, other: impl IntoLogicStateRef<'other>, width: BitWidth) -> bool {
+        self.repr
+            .eq_width(&other.into_logic_state_ref().repr, width)
+    }
+
+    /// Converts the state into bytes
+    ///
+    /// Returns `None` if any bit is high impedance or undefined
+    #[inline]
+    pub fn to_bytes(&self, endianness: Endianness) -> Option<Vec<u8>> {
+        self.repr.to_bytes(endianness)
+    }
+
+    /// Formats the state as an unsigned decimal number
+    ///
+    /// Falls back to the bit representation if any bit is high impedance or undefined
+    pub fn display_unsigned(&self) -> String {
+        self.repr
+            .to_decimal(false)
+            .unwrap_or_else(|| self.repr.to_string())
+    }
+
+    /// Formats the state as a signed two's complement decimal number
+    ///
+    /// Falls back to the bit representation if any bit is high impedance or undefined
+    pub fn display_signed(&self) -> String {
+        self.repr
+            .to_decimal(true)
+            .unwrap_or_else(|| self.repr.to_string())
+    }
+
     /// Turns the logic state into an owned form
     pub fn to_owned(&self) -> LogicState {
         if let LogicStateRepr::Ptr {
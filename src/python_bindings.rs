@@ -108,13 +108,18 @@ create_exception!(
 impl From<AddComponentError> for PyErr {
     fn from(err: AddComponentError) -> Self {
         match err {
-            AddComponentError::TooManyComponents => ResourceLimitReachedError::new_err(()),
+            AddComponentError::TooManyComponents
+            | AddComponentError::MemoryTooLarge
+            | AddComponentError::ComponentLimitReached { .. } => {
+                ResourceLimitReachedError::new_err(())
+            }
             AddComponentError::InvalidWireId => InvalidWireIdError::new_err(()),
             AddComponentError::WireWidthMismatch => WireWidthMismatchError::new_err(()),
             AddComponentError::WireWidthIncompatible => WireWidthIncompatibleError::new_err(()),
             AddComponentError::OffsetOutOfRange => OffsetOutOfRangeError::new_err(()),
             AddComponentError::TooFewInputs => TooFewInputsError::new_err(()),
             AddComponentError::InvalidInputCount => InvalidInputCountError::new_err(()),
+            AddComponentError::ConflictingDrives => SimulationConflictError::new_err(()),
         }
     }
 }
@@ -125,6 +130,20 @@ impl From<crate::InvalidWireIdError> for PyErr {
     }
 }
 
+impl From<crate::SetWireDriveError> for PyErr {
+    fn from(err: crate::SetWireDriveError) -> Self {
+        match err {
+            crate::SetWireDriveError::InvalidWireId => InvalidWireIdError::new_err(()),
+            crate::SetWireDriveError::InvalidBitWidth { .. } => {
+                WireWidthMismatchError::new_err(err.to_string())
+            }
+            crate::SetWireDriveError::DrivenInternally { .. } => {
+                SimulationConflictError::new_err(err.to_string())
+            }
+        }
+    }
+}
+
 impl From<crate::InvalidComponentIdError> for PyErr {
     fn from(_: crate::InvalidComponentIdError) -> Self {
         InvalidComponentIdError::new_err(())
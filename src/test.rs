@@ -1,6 +1,8 @@
 use crate::*;
 
 mod component;
+mod logic;
+mod simulator;
 //#[cfg(feature = "dot-export")]
 //mod dot_export;
 //mod import;
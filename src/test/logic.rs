@@ -0,0 +1,217 @@
+use super::*;
+
+const WIDTH_8: BitWidth = bit_width!(8);
+const WIDTH_12: BitWidth = bit_width!(12);
+const WIDTH_16: BitWidth = bit_width!(16);
+const WIDTH_128: BitWidth = bit_width!(128);
+
+#[test]
+fn bytes_round_trip() {
+    let value: u128 = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210;
+
+    let state = LogicState::from_bytes(&value.to_le_bytes(), WIDTH_128, Endianness::Little);
+    assert_eq!(
+        state,
+        LogicState::from_big_int(WIDTH_128, &[0x76543210, 0xFEDCBA98, 0x89ABCDEF, 0x01234567]),
+    );
+    assert_eq!(
+        state.to_bytes(Endianness::Little).unwrap(),
+        value.to_le_bytes()
+    );
+    assert_eq!(
+        state.to_bytes(Endianness::Big).unwrap(),
+        value.to_be_bytes()
+    );
+
+    let state = LogicState::from_bytes(&value.to_be_bytes(), WIDTH_128, Endianness::Big);
+    assert_eq!(
+        state.to_bytes(Endianness::Big).unwrap(),
+        value.to_be_bytes()
+    );
+    assert_eq!(
+        state.borrow().to_bytes(Endianness::Little).unwrap(),
+        value.to_le_bytes()
+    );
+}
+
+#[test]
+fn bytes_partial_width() {
+    let state = LogicState::from_bytes(&[0xFF, 0xFF, 0xFF], WIDTH_12, Endianness::Little);
+    assert_eq!(state, LogicState::from_u32(0xFFF, WIDTH_12));
+    assert_eq!(state.to_bytes(Endianness::Little).unwrap(), [0xFF, 0x0F]);
+    assert_eq!(state.to_bytes(Endianness::Big).unwrap(), [0x0F, 0xFF]);
+
+    let state = LogicState::from_bytes(&[0xAB], WIDTH_12, Endianness::Big);
+    assert_eq!(state, LogicState::from_u32(0xAB, WIDTH_12));
+
+    assert!(LogicState::high_z(WIDTH_12)
+        .to_bytes(Endianness::Little)
+        .is_none());
+    assert!("01010101X101"
+        .parse::<LogicState>()
+        .unwrap()
+        .to_bytes(Endianness::Little)
+        .is_none());
+}
+
+#[test]
+fn matches_with_dont_cares() {
+    let actual: LogicState = "10110010".parse().unwrap();
+
+    for (expected, result) in [
+        ("10110010", true),
+        ("10110011", false),
+        ("XXXXXXXX", true),
+        ("ZZZZZZZZ", true),
+        ("1011XXXX", true),
+        ("1011ZZ10", true),
+        ("0011XXXX", false),
+        ("X0Z1X0Z0", true),
+        ("X0Z1X0Z1", false),
+    ] {
+        let expected: LogicState = expected.parse().unwrap();
+        assert_eq!(
+            actual.matches(&expected),
+            result,
+            "actual: {actual}  expected: {expected}"
+        );
+    }
+
+    let actual: LogicState = "1X0Z".parse().unwrap();
+    assert!(actual.matches(&"1X0Z".parse::<LogicState>().unwrap()));
+    assert!(actual.matches(&"1XXX".parse::<LogicState>().unwrap()));
+    assert!(!actual.matches(&"1100".parse::<LogicState>().unwrap()));
+    assert!(!actual.matches(&"1X00".parse::<LogicState>().unwrap()));
+
+    let wide = LogicState::from_big_int(WIDTH_128, &[0, 0, 0xDEAD_BEEF, 0]);
+    let expected = LogicState::from_bit_planes(
+        WIDTH_128,
+        &[u32::MAX, u32::MAX, 0xDEAD_BEEF, 0],
+        &[u32::MAX, u32::MAX, 0, 0],
+    );
+    assert!(wide.borrow().matches(&expected));
+    assert!(!wide.matches(LogicState::logic_0(WIDTH_128).borrow()));
+    assert!(!wide.matches(&LogicState::undefined(WIDTH_12)));
+}
+
+#[test]
+fn eq_within_width() {
+    let a = LogicState::from_u32(0x12A5, WIDTH_16);
+    let b = LogicState::from_u32(0xFFA5, WIDTH_16);
+    assert_ne!(a, b);
+    assert!(a.eq(&b, WIDTH_8));
+    assert!(a.borrow().eq(&b, WIDTH_8));
+    assert!(!a.eq(&b, WIDTH_16));
+
+    let narrow = LogicState::from_u32(0xA5, WIDTH_8);
+    assert_ne!(a, narrow);
+    assert!(a.eq(&narrow, WIDTH_8));
+    assert!(!a.eq(&narrow, WIDTH_16));
+
+    let wide_a = LogicState::from_big_int(WIDTH_128, &[1, 2, 3, 4]);
+    let wide_b = LogicState::from_big_int(WIDTH_128, &[1, 2, 5, 4]);
+    assert!(wide_a.eq(&wide_b, bit_width!(65)));
+    assert!(!wide_a.eq(&wide_b, bit_width!(66)));
+}
+
+#[test]
+fn display_signed_and_unsigned() {
+    let state = LogicState::from_u32(0xFF, WIDTH_8);
+    assert_eq!(state.display_unsigned(), "255");
+    assert_eq!(state.display_signed(), "-1");
+
+    let state = LogicState::from_u32(0x80, WIDTH_8);
+    assert_eq!(state.display_unsigned(), "128");
+    assert_eq!(state.display_signed(), "-128");
+
+    let state = LogicState::from_u32(0x7F, WIDTH_8);
+    assert_eq!(state.borrow().display_unsigned(), "127");
+    assert_eq!(state.borrow().display_signed(), "127");
+
+    let state = LogicState::from_u32(0, WIDTH_12);
+    assert_eq!(state.display_unsigned(), "0");
+    assert_eq!(state.display_signed(), "0");
+
+    let state = LogicState::from_big_int(WIDTH_128, &[u32::MAX; 4]);
+    assert_eq!(
+        state.display_unsigned(),
+        "340282366920938463463374607431768211455"
+    );
+    assert_eq!(state.display_signed(), "-1");
+
+    let state = LogicState::from_big_int(WIDTH_128, &[0, 0, 0, 0x80000000]);
+    assert_eq!(
+        state.display_signed(),
+        "-170141183460469231731687303715884105728"
+    );
+
+    let state = LogicState::high_z(WIDTH_8);
+    assert_eq!(state.display_signed(), "ZZZZZZZZ");
+}
+
+#[test]
+fn parse_prefixed() {
+    let state = LogicState::parse_prefixed("d123", WIDTH_8).unwrap();
+    assert_eq!(state, LogicState::from_u32(123, WIDTH_8));
+
+    let state = LogicState::parse_prefixed("h1F", WIDTH_8).unwrap();
+    assert_eq!(state, LogicState::from_u32(0x1F, WIDTH_8));
+    let state = LogicState::parse_prefixed("hab", WIDTH_8).unwrap();
+    assert_eq!(state, LogicState::from_u32(0xAB, WIDTH_8));
+
+    let state = LogicState::parse_prefixed("b1X0Z", bit_width!(4)).unwrap();
+    assert_eq!(state, "1X0Z".parse::<LogicState>().unwrap());
+    let state = LogicState::parse_prefixed("1X0Z", bit_width!(4)).unwrap();
+    assert_eq!(state, "1X0Z".parse::<LogicState>().unwrap());
+    let state = LogicState::parse_prefixed("1", WIDTH_8).unwrap();
+    assert_eq!(state, LogicState::from_u32(1, WIDTH_8));
+
+    let state = LogicState::parse_prefixed("d340282366920938463463374607431768211455", WIDTH_128);
+    assert_eq!(
+        state.unwrap(),
+        LogicState::from_big_int(WIDTH_128, &[u32::MAX; 4])
+    );
+    let state = LogicState::parse_prefixed("h123456789ABCDEF0", WIDTH_128).unwrap();
+    assert_eq!(
+        state,
+        LogicState::from_big_int(WIDTH_128, &[0x9ABCDEF0, 0x12345678])
+    );
+
+    assert!(matches!(
+        LogicState::parse_prefixed("h1G", WIDTH_8),
+        Err(LogicStateFromStrError::IllegalCharacter(b'G'))
+    ));
+    assert!(matches!(
+        LogicState::parse_prefixed("d", WIDTH_8),
+        Err(LogicStateFromStrError::InvalidBitWidth)
+    ));
+}
+
+#[test]
+fn parse_prefixed_over_width() {
+    assert!(LogicState::parse_prefixed("d255", WIDTH_8).is_ok());
+    assert!(matches!(
+        LogicState::parse_prefixed("d256", WIDTH_8),
+        Err(LogicStateFromStrError::ValueTooWide)
+    ));
+
+    assert!(LogicState::parse_prefixed("hFFF", WIDTH_12).is_ok());
+    assert!(matches!(
+        LogicState::parse_prefixed("h1000", WIDTH_12),
+        Err(LogicStateFromStrError::ValueTooWide)
+    ));
+
+    assert!(matches!(
+        LogicState::parse_prefixed("d340282366920938463463374607431768211456", WIDTH_128),
+        Err(LogicStateFromStrError::ValueTooWide)
+    ));
+
+    assert!(matches!(
+        LogicState::parse_prefixed("b101010101", WIDTH_8),
+        Err(LogicStateFromStrError::ValueTooWide)
+    ));
+    assert!(matches!(
+        LogicState::parse_prefixed("XXXXZZZZ0", WIDTH_8),
+        Err(LogicStateFromStrError::ValueTooWide)
+    ));
+}
@@ -0,0 +1,2317 @@
+use super::*;
+
+const WIDTH_1: BitWidth = bit_width!(1);
+const WIDTH_8: BitWidth = bit_width!(8);
+const WIDTH_16: BitWidth = bit_width!(16);
+
+#[test]
+fn acyclic() {
+    let mut builder = SimulatorBuilder::default();
+
+    let a = builder.add_wire(WIDTH_8).unwrap();
+    let b = builder.add_wire(WIDTH_8).unwrap();
+    let c = builder.add_wire(WIDTH_8).unwrap();
+    let sum = builder.add_wire(WIDTH_8).unwrap();
+    let product = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_add(a, b, sum).unwrap();
+    builder.add_mul(sum, c, product).unwrap();
+    builder.add_not_gate(product, output).unwrap();
+
+    let mut sim = builder.build_acyclic().unwrap();
+
+    for (va, vb, vc) in [(1, 2, 3), (10, 20, 2), (255, 1, 7)] {
+        sim.set_wire_drive(a, &LogicState::from_u32(va, WIDTH_8))
+            .unwrap();
+        sim.set_wire_drive(b, &LogicState::from_u32(vb, WIDTH_8))
+            .unwrap();
+        sim.set_wire_drive(c, &LogicState::from_u32(vc, WIDTH_8))
+            .unwrap();
+        sim.run_sim(0).unwrap();
+
+        let expected = !(va.wrapping_add(vb).wrapping_mul(vc)) & 0xFF;
+        let [output_state, _] = sim.get_wire_state_and_drive(output).unwrap();
+        assert_eq!(output_state, LogicState::from_u32(expected, WIDTH_8));
+    }
+}
+
+#[test]
+fn acyclic_conflict() {
+    let mut builder = SimulatorBuilder::default();
+
+    let a = builder.add_wire(WIDTH_1).unwrap();
+    let b = builder.add_wire(WIDTH_1).unwrap();
+    let output = builder.add_wire(WIDTH_1).unwrap();
+    builder.add_not_gate(a, output).unwrap();
+    builder.add_not_gate(b, output).unwrap();
+    builder
+        .set_wire_drive(a, &LogicState::from_bool(false))
+        .unwrap();
+    builder
+        .set_wire_drive(b, &LogicState::from_bool(true))
+        .unwrap();
+
+    let mut sim = builder.build_acyclic().unwrap();
+    match sim.run_sim(0) {
+        SimulationRunResult::Err(err) => assert_eq!(&*err.conflicts, &[output]),
+        result => panic!("expected conflict, got {result:?}"),
+    }
+}
+
+#[test]
+fn acyclic_rejects_cycles() {
+    let mut builder = SimulatorBuilder::default();
+
+    let a = builder.add_wire(WIDTH_1).unwrap();
+    let b = builder.add_wire(WIDTH_1).unwrap();
+    let output = builder.add_wire(WIDTH_1).unwrap();
+    builder.add_and_gate(&[a, output], b).unwrap();
+    builder.add_not_gate(b, output).unwrap();
+    builder
+        .set_wire_drive(a, &LogicState::from_bool(false))
+        .unwrap();
+
+    let Err(err) = builder.build_acyclic() else {
+        panic!("cycle was not detected");
+    };
+    let mut sim = err.into_builder().build();
+    sim.run_sim(4).unwrap();
+
+    let [output_state, _] = sim.get_wire_state_and_drive(output).unwrap();
+    assert_eq!(output_state, LogicState::from_bool(true));
+}
+
+#[test]
+fn acyclic_register_feedback() {
+    let mut builder = SimulatorBuilder::default();
+
+    let clock = builder.add_wire(WIDTH_1).unwrap();
+    let data_in = builder.add_wire(WIDTH_1).unwrap();
+    let data_out = builder.add_wire(WIDTH_1).unwrap();
+    builder
+        .add_delay(data_in, data_out, clock, ClockPolarity::Rising)
+        .unwrap();
+    builder.add_not_gate(data_out, data_in).unwrap();
+
+    // The feedback path goes through a register, so it is not a combinational cycle
+    let mut sim = builder.build_acyclic().unwrap();
+    sim.set_wire_drive(clock, &LogicState::from_bool(false))
+        .unwrap();
+    sim.write_register_by_output(data_out, &LogicState::from_bool(false))
+        .unwrap();
+    sim.run_sim(0).unwrap();
+
+    for expected in [true, false, true, false] {
+        sim.set_wire_drive(clock, &LogicState::from_bool(true))
+            .unwrap();
+        sim.run_sim(0).unwrap();
+        sim.set_wire_drive(clock, &LogicState::from_bool(false))
+            .unwrap();
+        sim.run_sim(0).unwrap();
+
+        let [output_state, _] = sim.get_wire_state_and_drive(data_out).unwrap();
+        assert_eq!(output_state, LogicState::from_bool(expected));
+    }
+}
+
+#[test]
+fn acyclic_ram_feedback() {
+    let mut builder = SimulatorBuilder::default();
+
+    let clock = builder.add_wire(WIDTH_1).unwrap();
+    let addr = builder.add_wire(bit_width!(2)).unwrap();
+    let write = builder.add_wire(WIDTH_1).unwrap();
+    let one = builder.add_wire(WIDTH_8).unwrap();
+    let mask = builder.add_wire(WIDTH_8).unwrap();
+    let sum = builder.add_wire(WIDTH_8).unwrap();
+    let data_in = builder.add_wire(WIDTH_8).unwrap();
+    let data_out = builder.add_wire(WIDTH_8).unwrap();
+    builder
+        .add_ram(
+            addr,
+            data_in,
+            addr,
+            data_out,
+            write,
+            clock,
+            ClockPolarity::Rising,
+        )
+        .unwrap();
+    builder.add_add(data_out, one, sum).unwrap();
+    builder.add_and_gate(&[sum, mask], data_in).unwrap();
+    builder
+        .set_wire_drive(one, &LogicState::from_u32(1, WIDTH_8))
+        .unwrap();
+
+    // Incrementing an entry in place only feeds back through the clocked write port
+    let mut sim = builder.build_acyclic().unwrap();
+    sim.set_wire_drive(addr, &LogicState::from_u32(2, bit_width!(2)))
+        .unwrap();
+    sim.set_wire_drive(write, &LogicState::from_bool(true))
+        .unwrap();
+    sim.set_wire_drive(clock, &LogicState::from_bool(false))
+        .unwrap();
+    sim.run_sim(0).unwrap();
+
+    // Clear the entry first, since incrementing an undefined value keeps it undefined
+    for (mask_value, expected) in [(0x00, 0), (0xFF, 1), (0xFF, 2), (0xFF, 3)] {
+        sim.set_wire_drive(mask, &LogicState::from_u32(mask_value, WIDTH_8))
+            .unwrap();
+        sim.run_sim(0).unwrap();
+        for clock_state in [true, false] {
+            sim.set_wire_drive(clock, &LogicState::from_bool(clock_state))
+                .unwrap();
+            sim.run_sim(0).unwrap();
+        }
+
+        let [state, _] = sim.get_wire_state_and_drive(data_out).unwrap();
+        assert_eq!(state, LogicState::from_u32(expected, WIDTH_8));
+    }
+
+    let (cone_builder, wire_map) = sim.extract_cone(data_in).unwrap();
+    assert_eq!(cone_builder.component_count(), 2);
+    assert!(!wire_map.contains_key(&clock));
+    assert!(!wire_map.contains_key(&addr));
+}
+
+#[test]
+fn ordered_register_chain() {
+    fn build(downstream_first: bool) -> (SimulatorBuilder, [WireId; 4]) {
+        let mut builder = SimulatorBuilder::default();
+
+        let clock = builder.add_wire(WIDTH_1).unwrap();
+        let input = builder.add_wire(WIDTH_8).unwrap();
+        let stage_1 = builder.add_wire(WIDTH_8).unwrap();
+        let stage_2 = builder.add_wire(WIDTH_8).unwrap();
+        let first = |builder: &mut SimulatorBuilder| {
+            builder
+                .add_delay(input, stage_1, clock, ClockPolarity::Rising)
+                .unwrap();
+        };
+        let second = |builder: &mut SimulatorBuilder| {
+            builder
+                .add_delay(stage_1, stage_2, clock, ClockPolarity::Rising)
+                .unwrap();
+        };
+        if downstream_first {
+            second(&mut builder);
+            first(&mut builder);
+        } else {
+            first(&mut builder);
+            second(&mut builder);
+        }
+
+        (builder, [clock, input, stage_1, stage_2])
+    }
+
+    // Records both stages of the shift register after every rising edge
+    fn shift(mut sim: Simulator, [clock, input, stage_1, stage_2]: [WireId; 4]) -> Vec<String> {
+        sim.set_wire_drive(clock, &LogicState::from_bool(false))
+            .unwrap();
+        sim.run_sim(8).unwrap();
+
+        let mut stages = Vec::new();
+        for value in 1..=3 {
+            sim.set_wire_drive(input, &LogicState::from_u32(value, WIDTH_8))
+                .unwrap();
+            for clock_state in [true, false] {
+                sim.set_wire_drive(clock, &LogicState::from_bool(clock_state))
+                    .unwrap();
+                sim.run_sim(8).unwrap();
+            }
+
+            let [state_1, _] = sim.get_wire_state_and_drive(stage_1).unwrap();
+            let [state_2, _] = sim.get_wire_state_and_drive(stage_2).unwrap();
+            stages.push(format!("{state_1}/{state_2}"));
+        }
+        stages
+    }
+
+    let undefined = LogicState::undefined(WIDTH_8);
+    let value = |value| LogicState::from_u32(value, WIDTH_8);
+    let expected = [
+        format!("{}/{undefined}", value(1)),
+        format!("{}/{}", value(2), value(1)),
+        format!("{}/{}", value(3), value(2)),
+    ];
+
+    for downstream_first in [false, true] {
+        let (builder, wires) = build(downstream_first);
+        assert_eq!(shift(builder.build(), wires), expected);
+
+        let (builder, wires) = build(downstream_first);
+        let sim = builder.build_acyclic().unwrap();
+        assert_eq!(shift(sim, wires), expected, "acyclic {downstream_first}");
+
+        let (builder, wires) = build(downstream_first);
+        let sim = builder.build_with_ordered_start();
+        assert_eq!(
+            shift(sim, wires),
+            expected,
+            "ordered start {downstream_first}"
+        );
+    }
+}
+
+#[test]
+fn step_histogram() {
+    let mut builder = SimulatorBuilder::default();
+
+    let a = builder.add_wire(WIDTH_8).unwrap();
+    let b = builder.add_wire(WIDTH_8).unwrap();
+    let c = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_not_gate(a, b).unwrap();
+    builder.add_not_gate(b, c).unwrap();
+    builder.add_not_gate(c, output).unwrap();
+
+    let mut sim = builder.build();
+    assert!(sim.step_histogram().is_none());
+    sim.enable_step_histogram();
+
+    for value in 0..10 {
+        sim.set_wire_drive(a, &LogicState::from_u32(value, WIDTH_8))
+            .unwrap();
+        sim.run_sim(8).unwrap();
+    }
+
+    let histogram = sim.step_histogram().unwrap();
+    assert_eq!(histogram.values().sum::<u64>(), 10);
+    assert!(histogram.keys().all(|&steps| steps > 1));
+
+    sim.clear_step_histogram();
+    assert!(sim.step_histogram().unwrap().is_empty());
+
+    sim.run_sim(8).unwrap();
+    assert_eq!(sim.step_histogram().unwrap().values().sum::<u64>(), 1);
+}
+
+#[test]
+fn clock_domains() {
+    let mut builder = SimulatorBuilder::default();
+
+    let fast_clock = builder.add_wire(WIDTH_1).unwrap();
+    let slow_clock = builder.add_wire(WIDTH_1).unwrap();
+    let fast_clock_inv = builder.add_wire(WIDTH_1).unwrap();
+    let slow_clock_inv = builder.add_wire(WIDTH_1).unwrap();
+    builder.add_not_gate(fast_clock, fast_clock_inv).unwrap();
+    builder.add_not_gate(slow_clock, slow_clock_inv).unwrap();
+
+    builder
+        .define_clock_domain("fast", fast_clock, ClockPolarity::Rising)
+        .unwrap();
+    builder
+        .define_clock_domain("slow", slow_clock, ClockPolarity::Falling)
+        .unwrap();
+    assert!(matches!(
+        builder.define_clock_domain("fast", slow_clock, ClockPolarity::Rising),
+        Err(DefineClockDomainError::DuplicateName),
+    ));
+
+    let wide = builder.add_wire(WIDTH_8).unwrap();
+    assert!(matches!(
+        builder.define_clock_domain("wide", wide, ClockPolarity::Rising),
+        Err(DefineClockDomainError::WireWidthIncompatible),
+    ));
+
+    let mut sim = builder.build();
+    sim.run_sim(2).unwrap();
+    sim.enable_step_histogram();
+
+    for _ in 0..4 {
+        sim.tick("slow", 2).unwrap().unwrap();
+        for _ in 0..2 {
+            sim.tick("fast", 2).unwrap().unwrap();
+        }
+    }
+
+    // Every tick settles twice: once after each edge
+    let runs: u64 = sim.step_histogram().unwrap().values().sum();
+    assert_eq!(runs, (4 + 8) * 2);
+
+    let [fast_state, _] = sim.get_wire_state_and_drive(fast_clock_inv).unwrap();
+    assert_eq!(fast_state, LogicState::from_bool(true));
+    let [slow_state, _] = sim.get_wire_state_and_drive(slow_clock_inv).unwrap();
+    assert_eq!(slow_state, LogicState::from_bool(false));
+
+    assert!(sim.tick("medium", 2).is_err());
+}
+
+#[test]
+fn extract_cone() {
+    let mut builder = SimulatorBuilder::default();
+
+    let a = builder.add_wire(WIDTH_8).unwrap();
+    let b = builder.add_wire(WIDTH_8).unwrap();
+    let c = builder.add_wire(WIDTH_8).unwrap();
+    let sum = builder.add_wire(WIDTH_8).unwrap();
+    let product = builder.add_wire(WIDTH_8).unwrap();
+    let unrelated = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_add(a, b, sum).unwrap();
+    builder.add_mul(sum, c, product).unwrap();
+    builder.add_not_gate(c, unrelated).unwrap();
+    builder.set_wire_name(sum, "sum").unwrap();
+
+    let mut sim = builder.build();
+    sim.set_wire_drive(a, &LogicState::from_u32(3, WIDTH_8))
+        .unwrap();
+    sim.set_wire_drive(b, &LogicState::from_u32(4, WIDTH_8))
+        .unwrap();
+    sim.set_wire_drive(c, &LogicState::from_u32(5, WIDTH_8))
+        .unwrap();
+    sim.run_sim(8).unwrap();
+
+    let (_, wire_map) = sim.extract_cone(sum).unwrap();
+    assert_eq!(wire_map.len(), 3);
+    assert!(!wire_map.contains_key(&c));
+    assert!(!wire_map.contains_key(&product));
+
+    let (cone_builder, wire_map) = sim.extract_cone(product).unwrap();
+    assert_eq!(wire_map.len(), 5);
+    assert!(!wire_map.contains_key(&unrelated));
+
+    let mut cone = cone_builder.build();
+    cone.run_sim(8).unwrap();
+    let [state, _] = cone.get_wire_state_and_drive(wire_map[&product]).unwrap();
+    assert_eq!(state, LogicState::from_u32(35, WIDTH_8));
+    assert_eq!(cone.get_wire_name(wire_map[&sum]).unwrap(), Some("sum"));
+}
+
+#[test]
+fn extract_cone_stops_at_registers() {
+    let mut builder = SimulatorBuilder::default();
+
+    let clock = builder.add_wire(WIDTH_1).unwrap();
+    let data_in = builder.add_wire(WIDTH_1).unwrap();
+    let data_out = builder.add_wire(WIDTH_1).unwrap();
+    builder
+        .add_delay(data_in, data_out, clock, ClockPolarity::Rising)
+        .unwrap();
+    builder.add_not_gate(data_out, data_in).unwrap();
+
+    let mut sim = builder.build();
+    sim.set_wire_drive(clock, &LogicState::from_bool(false))
+        .unwrap();
+    sim.write_register_by_output(data_out, &LogicState::from_bool(true))
+        .unwrap();
+    sim.run_sim(8).unwrap();
+
+    let (cone_builder, wire_map) = sim.extract_cone(data_in).unwrap();
+    assert_eq!(cone_builder.component_count(), 1);
+    assert_eq!(wire_map.len(), 2);
+    assert!(!wire_map.contains_key(&clock));
+
+    let mut cone = cone_builder.build();
+    cone.run_sim(8).unwrap();
+    let [state, _] = cone.get_wire_state_and_drive(wire_map[&data_in]).unwrap();
+    assert_eq!(state, LogicState::from_bool(false));
+}
+
+#[test]
+fn unified_error() {
+    fn build_and_run() -> Result<LogicState, GsimError> {
+        let mut builder = SimulatorBuilder::default();
+        let input = builder.add_wire(WIDTH_8).unwrap();
+        let output = builder.add_wire(WIDTH_8).unwrap();
+        builder.add_not_gate(input, output)?;
+
+        let mut sim = builder.build_acyclic()?;
+        sim.set_wire_drive(input, &"10101010".parse::<LogicState>()?)?;
+        sim.run_sim(2).unwrap();
+        let [state, _] = sim.get_wire_state_and_drive(output)?;
+        Ok(state.to_owned())
+    }
+
+    let state = build_and_run().unwrap();
+    assert_eq!(state, "01010101".parse::<LogicState>().unwrap());
+
+    let err = GsimError::from("10a".parse::<LogicState>().unwrap_err());
+    assert!(matches!(err, GsimError::LogicStateFromStr(_)));
+    assert!(std::error::Error::source(&err).is_some());
+    assert_eq!(
+        err.to_string(),
+        "failed to parse logic state: illegal character `a`"
+    );
+
+    let err = GsimError::from(InvalidWireIdError);
+    assert!(matches!(err, GsimError::InvalidWireId(_)));
+}
+
+#[test]
+fn dump_state() {
+    let mut builder = SimulatorBuilder::default();
+
+    let input = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_not_gate(input, output).unwrap();
+    builder.set_wire_name(input, "input").unwrap();
+    builder.set_wire_name(output, "output").unwrap();
+
+    let mut sim = builder.build();
+    sim.set_wire_drive(input, &LogicState::from_u32(0x0F, WIDTH_8))
+        .unwrap();
+    sim.run_sim(2).unwrap();
+
+    let report = sim.dump_state();
+    assert!(report.contains("\"input\" [8]: state 00001111, drive 00001111"));
+    assert!(report.contains("\"output\" [8]: state 11110000, drive ZZZZZZZZ"));
+    assert!(report.contains("NotGate"));
+}
+
+#[test]
+fn collect_conflicts() {
+    // Conflicts are resolved on both the event-driven and the ordered path
+    for acyclic in [false, true] {
+        let mut builder = SimulatorBuilder::default();
+
+        let low = builder.add_wire(WIDTH_1).unwrap();
+        let high = builder.add_wire(WIDTH_1).unwrap();
+        let conflict_a = builder.add_wire(WIDTH_1).unwrap();
+        let conflict_b = builder.add_wire(WIDTH_1).unwrap();
+        let downstream = builder.add_wire(WIDTH_1).unwrap();
+        builder.add_not_gate(low, conflict_a).unwrap();
+        builder.add_not_gate(high, conflict_a).unwrap();
+        builder.add_buffer(low, high, conflict_b).unwrap();
+        builder.add_buffer(high, high, conflict_b).unwrap();
+        builder.add_not_gate(conflict_a, downstream).unwrap();
+        builder
+            .set_wire_drive(low, &LogicState::from_bool(false))
+            .unwrap();
+        builder
+            .set_wire_drive(high, &LogicState::from_bool(true))
+            .unwrap();
+
+        let mut sim = if acyclic {
+            builder.build_acyclic().unwrap()
+        } else {
+            builder.build()
+        };
+        let (result, conflicts) = sim.run_sim_collect_conflicts(8);
+        assert!(
+            matches!(result, SimulationRunResult::Ok),
+            "acyclic: {acyclic}"
+        );
+        assert_eq!(conflicts, [conflict_a, conflict_b], "acyclic: {acyclic}");
+
+        let [state, _] = sim.get_wire_state_and_drive(conflict_a).unwrap();
+        assert_eq!(state, LogicState::undefined(WIDTH_1), "acyclic: {acyclic}");
+        let [state, _] = sim.get_wire_state_and_drive(downstream).unwrap();
+        assert_eq!(state, LogicState::undefined(WIDTH_1), "acyclic: {acyclic}");
+    }
+}
+
+#[test]
+fn counts() {
+    let mut builder = SimulatorBuilder::default();
+    assert!(builder.is_empty());
+
+    let a = builder.add_wire(WIDTH_8).unwrap();
+    let b = builder.add_wire(WIDTH_8).unwrap();
+    let sum = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_add(a, b, sum).unwrap();
+    builder.add_not_gate(sum, output).unwrap();
+    builder.add_and_gate(&[a, b], output).unwrap();
+
+    assert!(!builder.is_empty());
+    assert_eq!(builder.wire_count(), 4);
+    assert_eq!(builder.component_count(), 3);
+
+    let sim = builder.build();
+    assert!(!sim.is_empty());
+    assert_eq!(sim.wire_count(), 4);
+    assert_eq!(sim.component_count(), 3);
+    assert_eq!(sim.iter_component_ids().count(), 3);
+}
+
+struct CountingAllocator;
+
+std::thread_local! {
+    static ALLOCATION_COUNT: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let _ = ALLOCATION_COUNT.try_with(|count| count.set(count.get() + 1));
+        unsafe { std::alloc::System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        unsafe { std::alloc::System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: std::alloc::Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATION_COUNT.try_with(|count| count.set(count.get() + 1));
+        unsafe { std::alloc::System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn with_capacity() {
+    const WIRE_COUNT: usize = 1000;
+
+    let mut builder = SimulatorBuilder::with_capacity(WIRE_COUNT, 0);
+
+    let allocations_before = ALLOCATION_COUNT.with(std::cell::Cell::get);
+    for _ in 0..WIRE_COUNT {
+        builder.add_wire(WIDTH_8).unwrap();
+    }
+    let allocations_after = ALLOCATION_COUNT.with(std::cell::Cell::get);
+
+    assert_eq!(allocations_after, allocations_before);
+    assert_eq!(builder.wire_count(), WIRE_COUNT);
+
+    let mut builder = SimulatorBuilder::default();
+
+    let allocations_before = ALLOCATION_COUNT.with(std::cell::Cell::get);
+    for _ in 0..WIRE_COUNT {
+        builder.add_wire(WIDTH_8).unwrap();
+    }
+    let allocations_after = ALLOCATION_COUNT.with(std::cell::Cell::get);
+
+    assert!(allocations_after > allocations_before);
+}
+
+#[test]
+fn with_capacity_components() {
+    const GATE_COUNT: usize = 1000;
+
+    let mut builder = SimulatorBuilder::with_capacity(2 * GATE_COUNT, GATE_COUNT);
+    builder.reserve_components(ComponentKind::NotGate, GATE_COUNT);
+
+    let allocations_before = ALLOCATION_COUNT.with(std::cell::Cell::get);
+    for _ in 0..GATE_COUNT {
+        let input = builder.add_wire(WIDTH_8).unwrap();
+        let output = builder.add_wire(WIDTH_8).unwrap();
+        builder.add_not_gate(input, output).unwrap();
+    }
+    let allocations_after = ALLOCATION_COUNT.with(std::cell::Cell::get);
+
+    assert_eq!(allocations_after, allocations_before);
+    assert_eq!(builder.component_count(), GATE_COUNT);
+}
+
+#[test]
+fn reset_all() {
+    let mut builder = SimulatorBuilder::default();
+
+    let input = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_not_gate(input, output).unwrap();
+
+    let mut sim = builder.build();
+    sim.set_wire_drive(input, &LogicState::from_u32(0x0F, WIDTH_8))
+        .unwrap();
+    sim.run_sim(2).unwrap();
+
+    sim.reset();
+    let [_, drive] = sim.get_wire_state_and_drive(input).unwrap();
+    assert_eq!(drive, LogicState::from_u32(0x0F, WIDTH_8));
+    sim.run_sim(2).unwrap();
+    let [state, _] = sim.get_wire_state_and_drive(output).unwrap();
+    assert_eq!(state, LogicState::from_u32(0xF0, WIDTH_8));
+
+    sim.reset_all();
+    let [state, drive] = sim.get_wire_state_and_drive(input).unwrap();
+    assert_eq!(state, LogicState::high_z(WIDTH_8));
+    assert_eq!(drive, LogicState::high_z(WIDTH_8));
+    sim.run_sim(2).unwrap();
+    let [state, _] = sim.get_wire_state_and_drive(output).unwrap();
+    assert_eq!(state, LogicState::undefined(WIDTH_8));
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn logged_run() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingLogger(AtomicUsize);
+
+    impl log::Log for CountingLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.target() == "gsim::sim"
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CountingLogger = CountingLogger(AtomicUsize::new(0));
+
+    fn run() -> LogicState {
+        let mut builder = SimulatorBuilder::default();
+
+        let a = builder.add_wire(WIDTH_8).unwrap();
+        let b = builder.add_wire(WIDTH_8).unwrap();
+        let c = builder.add_wire(WIDTH_8).unwrap();
+        let output = builder.add_wire(WIDTH_8).unwrap();
+        builder.add_not_gate(a, b).unwrap();
+        builder.add_add(a, b, c).unwrap();
+        builder.add_not_gate(c, output).unwrap();
+
+        let mut sim = builder.build();
+        sim.set_wire_drive(a, &LogicState::from_u32(0x35, WIDTH_8))
+            .unwrap();
+        sim.run_sim(8).unwrap();
+
+        let [state, _] = sim.get_wire_state_and_drive(output).unwrap();
+        state.to_owned()
+    }
+
+    let unlogged = run();
+
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+    let logged = run();
+    log::set_max_level(log::LevelFilter::Off);
+
+    assert_eq!(logged, unlogged);
+    assert_eq!(logged, LogicState::from_u32(0x00, WIDTH_8));
+    assert!(LOGGER.0.load(Ordering::Relaxed) > 0);
+}
+
+#[test]
+fn frozen_netlist() {
+    let mut builder = SimulatorBuilder::default();
+
+    let a = builder.add_wire(WIDTH_8).unwrap();
+    let b = builder.add_wire(WIDTH_8).unwrap();
+    let sum = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_add(a, b, sum).unwrap();
+    builder.set_wire_name(sum, "sum").unwrap();
+    builder
+        .set_wire_drive(b, &LogicState::from_u32(10, WIDTH_8))
+        .unwrap();
+
+    let netlist = builder.freeze();
+    assert_eq!(netlist.wire_count(), 3);
+    assert_eq!(netlist.component_count(), 1);
+
+    let handles: Vec<_> = (0..4)
+        .map(|value| {
+            let netlist = Arc::clone(&netlist);
+            std::thread::spawn(move || {
+                let mut sim = netlist.instantiate();
+                sim.set_wire_drive(a, &LogicState::from_u32(value, WIDTH_8))
+                    .unwrap();
+                sim.run_sim(2).unwrap();
+
+                assert_eq!(sim.get_wire_name(sum).unwrap(), Some("sum"));
+                let [state, _] = sim.get_wire_state_and_drive(sum).unwrap();
+                assert_eq!(state, LogicState::from_u32(value + 10, WIDTH_8));
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let mut sim = netlist.instantiate();
+    sim.run_sim(2).unwrap();
+    let [state, _] = sim.get_wire_state_and_drive(sum).unwrap();
+    assert_eq!(state, LogicState::undefined(WIDTH_8));
+
+    // The topology is shared with the netlist instead of being copied
+    assert!(Arc::ptr_eq(&sim.data.wires, &netlist.data.wires));
+    assert!(Arc::ptr_eq(&sim.data.wire_names, &netlist.data.wire_names));
+}
+
+#[test]
+fn dump_state_signed() {
+    let mut builder = SimulatorBuilder::default();
+
+    let register = builder.add_wire(WIDTH_8).unwrap();
+    builder.set_wire_name(register, "register").unwrap();
+    let inverted = builder.add_wire(WIDTH_8).unwrap();
+    builder.set_wire_name(inverted, "inverted").unwrap();
+    builder.add_not_gate(register, inverted).unwrap();
+
+    let mut sim = builder.build();
+    sim.set_wire_drive(register, &LogicState::from_u32(0xFF, WIDTH_8))
+        .unwrap();
+    sim.run_sim(2).unwrap();
+
+    let report = sim.dump_state_with_format(ValueFormat::Signed);
+    assert!(report.contains("\"register\" [8]: state -1, drive -1"));
+    let report = sim.dump_state_with_format(ValueFormat::Unsigned);
+    assert!(report.contains("\"register\" [8]: state 255, drive 255"));
+    assert!(report.contains("\"inverted\" [8]: state 0, drive Z"));
+}
+
+#[test]
+fn tie_wires() {
+    let mut builder = SimulatorBuilder::default();
+
+    let input = builder.add_wire(WIDTH_8).unwrap();
+    let port = builder.add_wire(WIDTH_8).unwrap();
+    let net = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    let narrow = builder.add_wire(WIDTH_1).unwrap();
+    let port_driver = builder.add_not_gate(input, port).unwrap();
+    builder.add_not_gate(net, output).unwrap();
+    builder.tie_wires(net, port).unwrap();
+    assert!(matches!(
+        builder.tie_wires(port, narrow),
+        Err(AddComponentError::WireWidthMismatch)
+    ));
+
+    let mut sim = builder.build();
+    sim.set_wire_drive(input, &LogicState::from_u32(0x0F, WIDTH_8))
+        .unwrap();
+    sim.run_sim(2).unwrap();
+
+    let [port_state, _] = sim.get_wire_state_and_drive(port).unwrap();
+    let [net_state, _] = sim.get_wire_state_and_drive(net).unwrap();
+    let [output_state, _] = sim.get_wire_state_and_drive(output).unwrap();
+    assert_eq!(port_state, LogicState::from_u32(0xF0, WIDTH_8));
+    assert_eq!(net_state, LogicState::from_u32(0xF0, WIDTH_8));
+    assert_eq!(output_state, LogicState::from_u32(0x0F, WIDTH_8));
+
+    // Driving the tied wire itself would conflict with the component driving the other one
+    assert!(matches!(
+        sim.set_wire_drive(net, &LogicState::from_u32(0x00, WIDTH_8)),
+        Err(SetWireDriveError::DrivenInternally { wire, component })
+            if (wire == port) && (component == port_driver)
+    ));
+}
+
+#[test]
+fn tie_wires_drives() {
+    let mut builder = SimulatorBuilder::default();
+
+    let a = builder.add_wire(WIDTH_8).unwrap();
+    let b = builder.add_wire(WIDTH_8).unwrap();
+    let c = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_not_gate(a, output).unwrap();
+    builder
+        .set_wire_drive(b, &LogicState::from_u32(0x0F, WIDTH_8))
+        .unwrap();
+    builder
+        .set_wire_drive(c, &LogicState::from_u32(0x33, WIDTH_8))
+        .unwrap();
+
+    // The drive of the tied wire is moved to the root, which shares its state right away
+    builder.tie_wires(b, a).unwrap();
+    let [_, drive] = builder.data.get_wire_state_and_drive(a).unwrap();
+    assert_eq!(drive, LogicState::from_u32(0x0F, WIDTH_8));
+    assert_eq!(
+        builder.data.wires.get(a).unwrap().state_id(),
+        builder.data.wires.get(b).unwrap().state_id()
+    );
+
+    assert!(matches!(
+        builder.tie_wires(c, b),
+        Err(AddComponentError::ConflictingDrives)
+    ));
+
+    let mut sim = builder.build();
+    sim.run_sim(2).unwrap();
+    let [output_state, _] = sim.get_wire_state_and_drive(output).unwrap();
+    assert_eq!(output_state, LogicState::from_u32(0xF0, WIDTH_8));
+}
+
+#[test]
+fn memory_checkpoint() {
+    let mut builder = SimulatorBuilder::default();
+
+    let data_in = builder.add_wire(WIDTH_16).unwrap();
+    let data_out = builder.add_wire(WIDTH_16).unwrap();
+    let push = builder.add_wire(WIDTH_1).unwrap();
+    let pop = builder.add_wire(WIDTH_1).unwrap();
+    let clock = builder.add_wire(WIDTH_1).unwrap();
+    let full = builder.add_wire(WIDTH_1).unwrap();
+    let empty = builder.add_wire(WIDTH_1).unwrap();
+    builder
+        .add_fifo(
+            data_in,
+            data_out,
+            push,
+            pop,
+            clock,
+            full,
+            empty,
+            NonZeroUsize::new(2).unwrap(),
+            ClockPolarity::Rising,
+        )
+        .unwrap();
+
+    let mut sim = builder.build();
+    sim.set_wire_drive(clock, &LogicState::from_bool(false))
+        .unwrap();
+    sim.run_sim(4).unwrap();
+
+    let write = |sim: &mut Simulator, value: u32, pop_value: bool| {
+        sim.set_wire_drive(data_in, &LogicState::from_u32(value, WIDTH_16))
+            .unwrap();
+        sim.set_wire_drive(push, &LogicState::from_bool(true))
+            .unwrap();
+        sim.set_wire_drive(pop, &LogicState::from_bool(pop_value))
+            .unwrap();
+        sim.set_wire_drive(clock, &LogicState::from_bool(true))
+            .unwrap();
+        sim.run_sim(4).unwrap();
+        sim.set_wire_drive(clock, &LogicState::from_bool(false))
+            .unwrap();
+        sim.run_sim(4).unwrap();
+    };
+
+    let read = |sim: &Simulator| {
+        let [data_state, _] = sim.get_wire_state_and_drive(data_out).unwrap();
+        let [full_state, _] = sim.get_wire_state_and_drive(full).unwrap();
+        (data_state.to_owned(), full_state.to_owned())
+    };
+
+    write(&mut sim, 1, false);
+    let checkpoint = sim.checkpoint_memory();
+    assert_eq!(
+        read(&sim),
+        (
+            LogicState::from_u32(1, WIDTH_16),
+            LogicState::from_bool(false)
+        )
+    );
+
+    // Overwrite the front entry and fill the FIFO
+    write(&mut sim, 2, true);
+    write(&mut sim, 3, false);
+    assert_eq!(
+        read(&sim),
+        (
+            LogicState::from_u32(2, WIDTH_16),
+            LogicState::from_bool(true)
+        )
+    );
+
+    sim.restore_memory(&checkpoint).unwrap();
+    sim.run_sim(4).unwrap();
+    assert_eq!(
+        read(&sim),
+        (
+            LogicState::from_u32(1, WIDTH_16),
+            LogicState::from_bool(false)
+        )
+    );
+}
+
+#[test]
+fn restore_memory_mismatch() {
+    let build_ram = |addr_width: BitWidth| {
+        let mut builder = SimulatorBuilder::default();
+        let addr = builder.add_wire(addr_width).unwrap();
+        let data_in = builder.add_wire(WIDTH_8).unwrap();
+        let data_out = builder.add_wire(WIDTH_8).unwrap();
+        let write = builder.add_wire(WIDTH_1).unwrap();
+        let clock = builder.add_wire(WIDTH_1).unwrap();
+        builder
+            .add_ram(
+                addr,
+                data_in,
+                addr,
+                data_out,
+                write,
+                clock,
+                ClockPolarity::Rising,
+            )
+            .unwrap();
+        builder.build()
+    };
+
+    let mut sim = build_ram(bit_width!(2));
+    let checkpoint = sim.checkpoint_memory();
+    assert!(sim.restore_memory(&checkpoint).is_ok());
+
+    // A RAM with a different number of entries
+    let other = build_ram(bit_width!(3));
+    assert!(matches!(
+        sim.restore_memory(&other.checkpoint_memory()),
+        Err(TopologyMismatchError)
+    ));
+
+    // No memory components at all
+    let mut empty = SimulatorBuilder::default().build();
+    assert!(matches!(
+        sim.restore_memory(&empty.checkpoint_memory()),
+        Err(TopologyMismatchError)
+    ));
+    assert!(matches!(
+        empty.restore_memory(&checkpoint),
+        Err(TopologyMismatchError)
+    ));
+}
+
+#[test]
+fn evaluate_combinational() {
+    let mut builder = SimulatorBuilder::default();
+
+    let inputs: Vec<_> = (0..4).map(|_| builder.add_wire(WIDTH_1).unwrap()).collect();
+    let left = builder.add_wire(WIDTH_1).unwrap();
+    let right = builder.add_wire(WIDTH_1).unwrap();
+    let output = builder.add_wire(WIDTH_1).unwrap();
+    builder.add_and_gate(&inputs[..2], left).unwrap();
+    builder.add_and_gate(&inputs[2..], right).unwrap();
+    builder.add_and_gate(&[left, right], output).unwrap();
+
+    let mut sim = builder.build();
+    for (values, expected) in [
+        ([true, true, true, true], true),
+        ([true, true, false, true], false),
+        ([false, true, true, true], false),
+    ] {
+        for (&input, value) in inputs.iter().zip(values) {
+            sim.set_wire_drive(input, &LogicState::from_bool(value))
+                .unwrap();
+        }
+
+        sim.evaluate_combinational().unwrap();
+
+        let [output_state, _] = sim.get_wire_state_and_drive(output).unwrap();
+        assert_eq!(output_state, LogicState::from_bool(expected));
+    }
+
+    let mut builder = SimulatorBuilder::default();
+
+    let wire = builder.add_wire(WIDTH_1).unwrap();
+    builder.add_not_gate(wire, wire).unwrap();
+
+    let mut sim = builder.build();
+    assert!(matches!(
+        sim.evaluate_combinational(),
+        Err(NotCombinationalError::HasCycles)
+    ));
+}
+
+#[test]
+fn component_limit() {
+    const CAPACITY: usize = 3;
+
+    let mut builder = SimulatorBuilder::default();
+    builder.data.components.set_kind_capacity(CAPACITY);
+
+    let input = builder.add_wire(WIDTH_1).unwrap();
+    for added in 0..CAPACITY {
+        assert_eq!(
+            builder.remaining_component_capacity(ComponentKind::NotGate),
+            CAPACITY - added
+        );
+
+        let output = builder.add_wire(WIDTH_1).unwrap();
+        builder.add_not_gate(input, output).unwrap();
+    }
+
+    assert_eq!(
+        builder.remaining_component_capacity(ComponentKind::NotGate),
+        0
+    );
+    assert_eq!(
+        builder.remaining_component_capacity(ComponentKind::Buffer),
+        CAPACITY
+    );
+
+    let output = builder.add_wire(WIDTH_1).unwrap();
+    assert!(matches!(
+        builder.add_not_gate(input, output),
+        Err(AddComponentError::ComponentLimitReached {
+            kind: ComponentKind::NotGate,
+            count: CAPACITY,
+        })
+    ));
+
+    // The rejected component must not be left connected to its wires
+    assert!(builder.data.wires.get(output).unwrap().drivers().is_empty());
+    assert_eq!(
+        builder.data.wires.get(input).unwrap().driving().len(),
+        CAPACITY
+    );
+
+    let enable = builder.add_wire(WIDTH_1).unwrap();
+    builder.add_buffer(input, enable, output).unwrap();
+}
+
+#[test]
+fn contended_bus_is_deterministic() {
+    const DRIVER_COUNT: usize = 16;
+
+    // Adds the drivers in the given order, so the same driver ends up with a different output state each time
+    fn resolve_bus(order: &[usize], enabled: &[usize]) -> (LogicState, Vec<WireId>) {
+        let mut builder = SimulatorBuilder::default();
+
+        let bus = builder.add_wire(WIDTH_8).unwrap();
+        for &index in order {
+            let input = builder.add_wire(WIDTH_8).unwrap();
+            let enable = builder.add_wire(WIDTH_1).unwrap();
+            builder.add_buffer(input, enable, bus).unwrap();
+            builder
+                .set_wire_drive(input, &LogicState::from_u32(index as u32, WIDTH_8))
+                .unwrap();
+            builder
+                .set_wire_drive(enable, &LogicState::from_bool(enabled.contains(&index)))
+                .unwrap();
+        }
+
+        let drivers = builder.data.wires.get(bus).unwrap().drivers();
+        assert!(drivers.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let mut sim = builder.build();
+        let (result, conflicts) = sim.run_sim_collect_conflicts(8);
+        assert!(matches!(result, SimulationRunResult::Ok));
+
+        let [state, _] = sim.get_wire_state_and_drive(bus).unwrap();
+        (state.to_owned(), conflicts)
+    }
+
+    let forward: Vec<_> = (0..DRIVER_COUNT).collect();
+    let mut orders = vec![forward.clone(), forward.iter().rev().copied().collect()];
+    for rotation in 1..DRIVER_COUNT {
+        let mut rotated = forward.clone();
+        rotated.rotate_left(rotation);
+        orders.push(rotated);
+    }
+    // Even drivers first, then odd ones
+    orders.push(
+        forward
+            .iter()
+            .copied()
+            .filter(|index| index % 2 == 0)
+            .chain(forward.iter().copied().filter(|index| index % 2 == 1))
+            .collect(),
+    );
+    // A fixed pseudo-random shuffle
+    let mut shuffled = forward.clone();
+    let mut seed = 0x2545_F491_u32;
+    for i in (1..DRIVER_COUNT).rev() {
+        seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        shuffled.swap(i, (seed as usize) % (i + 1));
+    }
+    orders.push(shuffled);
+
+    for enabled in [&[][..], &[5], &[3, 11], &[0, 7, 15]] {
+        let expected = resolve_bus(&forward, enabled);
+        for order in &orders {
+            for _ in 0..4 {
+                assert_eq!(resolve_bus(order, enabled), expected, "order {order:?}");
+            }
+        }
+    }
+
+    let (state, conflicts) = resolve_bus(&forward, &[5]);
+    assert_eq!(state, LogicState::from_u32(5, WIDTH_8));
+    assert!(conflicts.is_empty());
+
+    let (state, conflicts) = resolve_bus(&forward, &[3, 11]);
+    assert_eq!(state, LogicState::undefined(WIDTH_8));
+    assert_eq!(conflicts.len(), 1);
+}
+
+#[test]
+fn tied_wire_drivers_stay_sorted() {
+    let mut builder = SimulatorBuilder::default();
+
+    let bus_a = builder.add_wire(WIDTH_8).unwrap();
+    let bus_b = builder.add_wire(WIDTH_8).unwrap();
+
+    // The wire with the higher ID gets the drivers with the lower output state IDs,
+    // so tying it to the other wire adds them out of order
+    let mut add_driver = |bus: WireId, value: u32| {
+        let input = builder.add_wire(WIDTH_8).unwrap();
+        let enable = builder.add_wire(WIDTH_1).unwrap();
+        builder.add_buffer(input, enable, bus).unwrap();
+        builder
+            .set_wire_drive(input, &LogicState::from_u32(value, WIDTH_8))
+            .unwrap();
+        builder
+            .set_wire_drive(enable, &LogicState::from_bool(value == 2))
+            .unwrap();
+    };
+    add_driver(bus_b, 0);
+    add_driver(bus_b, 1);
+    add_driver(bus_a, 2);
+    add_driver(bus_a, 3);
+    builder.tie_wires(bus_a, bus_b).unwrap();
+
+    let mut sim = builder.build();
+    let drivers = sim.data.wires.get(bus_a).unwrap().drivers();
+    assert_eq!(drivers.len(), 4);
+    assert!(drivers.windows(2).all(|pair| pair[0] < pair[1]));
+
+    sim.run_sim(2).unwrap();
+    let [state, _] = sim.get_wire_state_and_drive(bus_b).unwrap();
+    assert_eq!(state, LogicState::from_u32(2, WIDTH_8));
+}
+
+#[test]
+fn iter_wires_and_components() {
+    let mut builder = SimulatorBuilder::default();
+
+    let input = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    let flag = builder.add_wire(WIDTH_1).unwrap();
+    let buffered = builder.add_wire(WIDTH_8).unwrap();
+    let not = builder.add_not_gate(input, output).unwrap();
+    let buffer = builder.add_buffer(output, flag, buffered).unwrap();
+    builder.set_wire_name(input, "input").unwrap();
+    builder.set_wire_name(flag, "flag").unwrap();
+    builder.set_component_name(not, "invert").unwrap();
+
+    let sim = builder.build();
+
+    let wires: Vec<_> = sim
+        .iter_wires()
+        .map(|wire| (wire.id, wire.name, wire.bit_width))
+        .collect();
+    assert_eq!(
+        wires,
+        [
+            (input, Some("input"), WIDTH_8),
+            (output, None, WIDTH_8),
+            (flag, Some("flag"), WIDTH_1),
+            (buffered, None, WIDTH_8),
+        ]
+    );
+
+    let components: Vec<_> = sim
+        .iter_components()
+        .map(|component| (component.id, component.kind, component.name))
+        .collect();
+    assert_eq!(components.len(), 2);
+    assert!(components.contains(&(not, ComponentKind::NotGate, Some("invert"))));
+    assert!(components.contains(&(buffer, ComponentKind::Buffer, None)));
+}
+
+#[test]
+fn component_names() {
+    let mut builder = SimulatorBuilder::default();
+
+    let input = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    let not = builder.add_not_gate(input, output).unwrap();
+    let missing = ComponentId::from_bits(not.to_bits() + 1);
+
+    assert_eq!(builder.get_component_name(not).unwrap(), None);
+    builder.set_component_name(not, "invert").unwrap();
+    assert_eq!(builder.get_component_name(not).unwrap(), Some("invert"));
+    assert!(matches!(
+        builder.set_component_name(missing, "missing"),
+        Err(InvalidComponentIdError)
+    ));
+    assert!(matches!(
+        builder.get_component_name(missing),
+        Err(InvalidComponentIdError)
+    ));
+
+    let sim = builder.build();
+    assert_eq!(sim.get_component_name(not).unwrap(), Some("invert"));
+    assert!(matches!(
+        sim.get_component_name(missing),
+        Err(InvalidComponentIdError)
+    ));
+}
+
+#[test]
+fn diff() {
+    fn build(a_value: bool, not_input_a: bool) -> (Simulator, [WireId; 5], ComponentId) {
+        let mut builder = SimulatorBuilder::default();
+
+        let a = builder.add_wire(WIDTH_1).unwrap();
+        let b = builder.add_wire(WIDTH_1).unwrap();
+        let and = builder.add_wire(WIDTH_1).unwrap();
+        let not = builder.add_wire(WIDTH_1).unwrap();
+        let clock = builder.add_wire(WIDTH_1).unwrap();
+        let delayed = builder.add_wire(WIDTH_1).unwrap();
+        builder.add_and_gate(&[a, b], and).unwrap();
+        builder
+            .add_not_gate(if not_input_a { a } else { b }, not)
+            .unwrap();
+        let delay = builder
+            .add_delay(a, delayed, clock, ClockPolarity::Rising)
+            .unwrap();
+        builder
+            .set_wire_drive(a, &LogicState::from_bool(a_value))
+            .unwrap();
+        builder
+            .set_wire_drive(b, &LogicState::from_bool(true))
+            .unwrap();
+        builder
+            .set_wire_drive(clock, &LogicState::from_bool(false))
+            .unwrap();
+
+        let mut sim = builder.build();
+        assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+        sim.set_wire_drive(clock, &LogicState::from_bool(true))
+            .unwrap();
+        assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+        (sim, [a, b, and, not, delayed], delay)
+    }
+
+    let (sim_a, [a, _, and, _, delayed], delay) = build(true, false);
+    let (sim_b, _, _) = build(false, false);
+
+    assert!(sim_a.diff(&sim_a).unwrap().is_empty());
+
+    let diff = sim_a.diff(&sim_b).unwrap();
+    let wires: Vec<_> = diff
+        .wires
+        .iter()
+        .map(|wire| {
+            (
+                wire.id,
+                wire.state.to_string(),
+                wire.other_state.to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        wires,
+        [
+            (a, "1".to_owned(), "0".to_owned()),
+            (and, "1".to_owned(), "0".to_owned()),
+            (delayed, "1".to_owned(), "0".to_owned()),
+        ]
+    );
+    let memories: Vec<_> = diff
+        .memories
+        .iter()
+        .map(|memory| {
+            (
+                memory.id,
+                memory
+                    .contents
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+                memory
+                    .other_contents
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        memories,
+        [(delay, vec!["1".to_owned()], vec!["0".to_owned()])]
+    );
+
+    let (rewired, _, _) = build(true, true);
+    assert!(matches!(sim_a.diff(&rewired), Err(TopologyMismatchError)));
+
+    let mut builder = SimulatorBuilder::default();
+    builder.add_wire(WIDTH_1).unwrap();
+    let other = builder.build();
+    assert!(matches!(sim_a.diff(&other), Err(TopologyMismatchError)));
+}
+
+#[test]
+fn mark_ports() {
+    let mut builder = SimulatorBuilder::default();
+
+    let a = builder.add_wire(WIDTH_8).unwrap();
+    let b = builder.add_wire(WIDTH_8).unwrap();
+    let sum = builder.add_wire(WIDTH_8).unwrap();
+    let add = builder.add_add(a, b, sum).unwrap();
+    builder.mark_input(a, "a").unwrap();
+    builder.mark_input(b, "b").unwrap();
+    builder.mark_output(sum, "sum").unwrap();
+    assert!(matches!(
+        builder.mark_input(WireId::from_bits(100), "c"),
+        Err(InvalidWireIdError)
+    ));
+    assert!(matches!(
+        builder.set_wire_drive(sum, &LogicState::from_u32(0, WIDTH_8)),
+        Err(SetWireDriveError::DrivenInternally { wire, component })
+            if (wire == sum) && (component == add)
+    ));
+
+    let mut sim = builder.build();
+    let ports = sim.ports();
+    assert_eq!(ports.inputs.len(), 2);
+    assert_eq!(ports.inputs["a"], a);
+    assert_eq!(ports.inputs["b"], b);
+    assert_eq!(ports.outputs.len(), 1);
+    assert_eq!(ports.outputs["sum"], sum);
+
+    for (name, value) in [("a", 3), ("b", 4)] {
+        let wire = sim.ports().inputs[name];
+        sim.set_wire_drive(wire, &LogicState::from_u32(value, WIDTH_8))
+            .unwrap();
+    }
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+
+    let [state, _] = sim
+        .get_wire_state_and_drive(sim.ports().outputs["sum"])
+        .unwrap();
+    assert_eq!(state, LogicState::from_u32(7, WIDTH_8));
+
+    // An output driven by a component can only be released
+    let err = sim
+        .set_wire_drive(sum, &LogicState::from_u32(0, WIDTH_8))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        SetWireDriveError::DrivenInternally { wire, component }
+            if (wire == sum) && (component == add)
+    ));
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "wire {} is driven by component {} and cannot also be driven externally",
+            sum.to_bits(),
+            add.to_bits()
+        )
+    );
+    sim.set_wire_drive(sum, &LogicState::high_z(WIDTH_8))
+        .unwrap();
+}
+
+#[test]
+fn state_fingerprint() {
+    let mut builder = SimulatorBuilder::default();
+
+    let input = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_not_gate(input, output).unwrap();
+    builder
+        .set_wire_drive(input, &LogicState::from_u32(0x0F, WIDTH_8))
+        .unwrap();
+
+    let mut sim = builder.build();
+    let initial = sim.state_fingerprint();
+
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+    let settled = sim.state_fingerprint();
+    assert_ne!(settled, initial);
+
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+    assert_eq!(sim.state_fingerprint(), settled);
+
+    sim.set_wire_drive(input, &LogicState::from_u32(0xF0, WIDTH_8))
+        .unwrap();
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+    let changed = sim.state_fingerprint();
+    assert_ne!(changed, settled);
+
+    sim.set_wire_drive(input, &LogicState::from_u32(0x0F, WIDTH_8))
+        .unwrap();
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+    assert_eq!(sim.state_fingerprint(), settled);
+}
+
+#[test]
+fn register_by_output() {
+    let mut builder = SimulatorBuilder::default();
+
+    let input = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    let inverted = builder.add_wire(WIDTH_8).unwrap();
+    let clock = builder.add_wire(WIDTH_1).unwrap();
+    builder
+        .add_delay(input, output, clock, ClockPolarity::Rising)
+        .unwrap();
+    builder.add_not_gate(output, inverted).unwrap();
+    builder
+        .set_wire_drive(input, &LogicState::from_u32(0x10, WIDTH_8))
+        .unwrap();
+    builder
+        .set_wire_drive(clock, &LogicState::from_bool(false))
+        .unwrap();
+
+    let mut sim = builder.build();
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+
+    sim.write_register_by_output(output, &LogicState::from_u32(0x42, WIDTH_8))
+        .unwrap();
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+    let [state, _] = sim.get_wire_state_and_drive(output).unwrap();
+    assert_eq!(state, LogicState::from_u32(0x42, WIDTH_8));
+    let [state, _] = sim.get_wire_state_and_drive(inverted).unwrap();
+    assert_eq!(state, LogicState::from_u32(0xBD, WIDTH_8));
+
+    sim.set_wire_drive(clock, &LogicState::from_bool(true))
+        .unwrap();
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+    let [state, _] = sim.get_wire_state_and_drive(output).unwrap();
+    assert_eq!(state, LogicState::from_u32(0x10, WIDTH_8));
+    assert_eq!(
+        sim.read_register_by_output(output).unwrap(),
+        LogicState::from_u32(0x10, WIDTH_8)
+    );
+
+    assert!(matches!(
+        sim.write_register_by_output(output, &LogicState::from_bool(true)),
+        Err(RegisterAccessError::WireWidthMismatch)
+    ));
+    assert!(matches!(
+        sim.read_register_by_output(inverted),
+        Err(RegisterAccessError::NoRegister)
+    ));
+    assert!(matches!(
+        sim.read_register_by_output(input),
+        Err(RegisterAccessError::NoRegister)
+    ));
+}
+
+#[test]
+fn run_sim_with_callback() {
+    const CHAIN_LEN: usize = 64;
+
+    fn build_chain() -> (Simulator, WireId) {
+        let mut builder = SimulatorBuilder::default();
+
+        let input = builder.add_wire(WIDTH_1).unwrap();
+        let mut output = input;
+        for _ in 0..CHAIN_LEN {
+            let next = builder.add_wire(WIDTH_1).unwrap();
+            builder.add_not_gate(output, next).unwrap();
+            output = next;
+        }
+
+        builder
+            .set_wire_drive(input, &LogicState::from_bool(false))
+            .unwrap();
+        (builder.build(), output)
+    }
+
+    let every_n_steps = NonZeroU64::new(10).unwrap();
+
+    let (mut sim, output) = build_chain();
+    sim.enable_step_histogram();
+    let mut reports = Vec::new();
+    let result = sim.run_sim_with_callback(1000, every_n_steps, |progress| {
+        reports.push(progress.steps);
+        ControlFlow::Continue(())
+    });
+    assert!(matches!(result, Some(SimulationRunResult::Ok)));
+    // The initial pass is followed by one step per gate, the last of which changes nothing
+    assert_eq!(
+        sim.step_histogram().unwrap().get(&(CHAIN_LEN as u64 + 1)),
+        Some(&1)
+    );
+    assert_eq!(reports, [10, 20, 30, 40, 50, 60]);
+    let [state, _] = sim.get_wire_state_and_drive(output).unwrap();
+    assert_eq!(state, LogicState::from_bool(false));
+
+    let (mut sim, output) = build_chain();
+    let mut calls = 0;
+    let result = sim.run_sim_with_callback(1000, every_n_steps, |_| {
+        calls += 1;
+        if calls == 2 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    assert!(result.is_none());
+    assert_eq!(calls, 2);
+    let [state, _] = sim.get_wire_state_and_drive(output).unwrap();
+    assert_eq!(state, LogicState::undefined(WIDTH_1));
+}
+
+#[test]
+fn iter_component_outputs() {
+    let mut builder = SimulatorBuilder::default();
+
+    let input_a = builder.add_wire(WIDTH_8).unwrap();
+    let input_b = builder.add_wire(WIDTH_8).unwrap();
+    let less = builder.add_wire(WIDTH_1).unwrap();
+    let equal = builder.add_wire(WIDTH_1).unwrap();
+    let greater = builder.add_wire(WIDTH_1).unwrap();
+    let inverted = builder.add_wire(WIDTH_8).unwrap();
+    let compare = builder
+        .add_magnitude_compare(input_a, input_b, less, equal, greater)
+        .unwrap();
+    let not = builder.add_not_gate(input_a, inverted).unwrap();
+    builder
+        .set_wire_drive(input_a, &LogicState::from_u32(0x30, WIDTH_8))
+        .unwrap();
+    builder
+        .set_wire_drive(input_b, &LogicState::from_u32(0x21, WIDTH_8))
+        .unwrap();
+
+    let mut sim = builder.build();
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+
+    let outputs: Vec<_> = sim
+        .iter_component_outputs()
+        .map(|(component, wire, state)| (component, wire, state.to_owned()))
+        .collect();
+    assert_eq!(outputs.len(), 4);
+
+    let compare_outputs: Vec<_> = outputs
+        .iter()
+        .filter(|&&(component, _, _)| component == compare)
+        .map(|(_, wire, state)| (*wire, state.to_string()))
+        .collect();
+    assert_eq!(
+        compare_outputs,
+        [
+            (less, "0".to_owned()),
+            (equal, "0".to_owned()),
+            (greater, "1".to_owned()),
+        ]
+    );
+
+    let not_output = outputs
+        .iter()
+        .find(|&&(component, _, _)| component == not)
+        .unwrap();
+    assert_eq!(not_output.1, inverted);
+    assert_eq!(not_output.2, LogicState::from_u32(0xCF, WIDTH_8));
+}
+
+#[test]
+fn extend_from() {
+    let mut builder = SimulatorBuilder::default();
+    let a = builder.add_wire(WIDTH_8).unwrap();
+    let b = builder.add_wire(WIDTH_8).unwrap();
+    let sum = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_add(a, b, sum).unwrap();
+    builder
+        .set_wire_drive(a, &LogicState::from_u32(3, WIDTH_8))
+        .unwrap();
+    builder
+        .set_wire_drive(b, &LogicState::from_u32(4, WIDTH_8))
+        .unwrap();
+
+    let mut other = SimulatorBuilder::default();
+    let c = other.add_wire(WIDTH_8).unwrap();
+    let d = other.add_wire(WIDTH_8).unwrap();
+    let product = other.add_wire(WIDTH_8).unwrap();
+    let mul = other.add_mul(c, d, product).unwrap();
+    other
+        .set_wire_drive(d, &LogicState::from_u32(5, WIDTH_8))
+        .unwrap();
+    other.set_wire_name(product, "product").unwrap();
+    other.mark_output(product, "product").unwrap();
+
+    let remap = builder.extend_from(other);
+    assert_eq!(builder.wire_count(), 6);
+    assert_eq!(builder.component_count(), 2);
+
+    let c = remap.wire(c).unwrap();
+    let product = remap.wire(product).unwrap();
+    assert!(remap.component(mul).is_some());
+    assert_eq!(builder.ports().outputs["product"], product);
+    builder.tie_wires(sum, c).unwrap();
+
+    let mut sim = builder.build();
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+    let [state, _] = sim.get_wire_state_and_drive(product).unwrap();
+    assert_eq!(state, LogicState::from_u32(35, WIDTH_8));
+    assert_eq!(sim.get_wire_name(product).unwrap(), Some("product"));
+}
+
+#[test]
+fn is_settled() {
+    let mut builder = SimulatorBuilder::default();
+
+    let input = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_not_gate(input, output).unwrap();
+
+    let mut sim = builder.build();
+    assert!(!sim.is_settled());
+
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+    assert!(sim.is_settled());
+
+    sim.set_wire_drive(input, &LogicState::from_u32(0x0F, WIDTH_8))
+        .unwrap();
+    assert!(!sim.is_settled());
+
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+    assert!(sim.is_settled());
+    let [state, _] = sim.get_wire_state_and_drive(output).unwrap();
+    assert_eq!(state, LogicState::from_u32(0xF0, WIDTH_8));
+
+    sim.reset();
+    assert!(!sim.is_settled());
+}
+
+#[test]
+fn wire_driver_contributions() {
+    let mut builder = SimulatorBuilder::default();
+
+    let input_a = builder.add_wire(WIDTH_8).unwrap();
+    let input_b = builder.add_wire(WIDTH_8).unwrap();
+    let enable_a = builder.add_wire(WIDTH_1).unwrap();
+    let enable_b = builder.add_wire(WIDTH_1).unwrap();
+    let bus = builder.add_wire(WIDTH_8).unwrap();
+    let buffer_a = builder.add_buffer(input_a, enable_a, bus).unwrap();
+    let buffer_b = builder.add_buffer(input_b, enable_b, bus).unwrap();
+    builder
+        .set_wire_drive(input_a, &LogicState::from_u32(0x0F, WIDTH_8))
+        .unwrap();
+    builder
+        .set_wire_drive(input_b, &LogicState::from_u32(0xF0, WIDTH_8))
+        .unwrap();
+    builder
+        .set_wire_drive(enable_a, &LogicState::from_bool(true))
+        .unwrap();
+    builder
+        .set_wire_drive(enable_b, &LogicState::from_bool(false))
+        .unwrap();
+
+    let mut sim = builder.build();
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+
+    let contributions = sim.wire_driver_contributions(bus).unwrap();
+    assert_eq!(contributions.len(), 2);
+    assert_eq!(contributions[0].0, buffer_a);
+    assert_eq!(contributions[0].1, LogicState::from_u32(0x0F, WIDTH_8));
+    assert_eq!(contributions[1].0, buffer_b);
+    assert_eq!(contributions[1].1, LogicState::all_high_z(WIDTH_8));
+    let [state, _] = sim.get_wire_state_and_drive(bus).unwrap();
+    assert_eq!(state, LogicState::from_u32(0x0F, WIDTH_8));
+
+    sim.set_wire_drive(enable_b, &LogicState::from_bool(true))
+        .unwrap();
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Err(_)));
+
+    let contributions = sim.wire_driver_contributions(bus).unwrap();
+    assert_eq!(contributions[0].1, LogicState::from_u32(0x0F, WIDTH_8));
+    assert_eq!(contributions[1].1, LogicState::from_u32(0xF0, WIDTH_8));
+
+    assert!(sim.wire_driver_contributions(input_a).unwrap().is_empty());
+}
+
+#[test]
+fn set_wire_drive_width_mismatch() {
+    let mut builder = SimulatorBuilder::default();
+
+    let wire = builder.add_wire(WIDTH_8).unwrap();
+    let err = builder
+        .set_wire_drive(wire, &LogicState::from_u32(0x5, bit_width!(4)))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        SetWireDriveError::InvalidBitWidth { expected, actual }
+            if (expected == WIDTH_8) && (actual == bit_width!(4))
+    ));
+    let msg = err.to_string();
+    assert!(msg.contains("expected width 8"), "{msg}");
+    assert!(msg.contains("got 4"), "{msg}");
+
+    let mut sim = builder.build();
+    assert!(matches!(
+        sim.set_wire_drive(wire, &LogicState::from_bool(true)),
+        Err(SetWireDriveError::InvalidBitWidth { .. })
+    ));
+    let [_, drive] = sim.get_wire_state_and_drive(wire).unwrap();
+    assert_eq!(drive, LogicState::high_z(WIDTH_8));
+
+    sim.set_wire_drive(wire, &LogicState::from_u32(0x5A, WIDTH_8))
+        .unwrap();
+}
+
+#[test]
+fn component_metadata() {
+    let mut builder = SimulatorBuilder::default();
+
+    let input = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    let inverted = builder.add_wire(WIDTH_8).unwrap();
+    let buffer = builder.add_wire(WIDTH_8).unwrap();
+    let not = builder.add_not_gate(input, output).unwrap();
+    let other = builder.add_not_gate(output, inverted).unwrap();
+    builder
+        .set_component_metadata(not, "source", "alu.v:12")
+        .unwrap();
+    builder
+        .set_component_metadata(not, "category", "datapath")
+        .unwrap();
+    builder
+        .set_component_metadata(not, "category", "control")
+        .unwrap();
+
+    assert_eq!(
+        builder.get_component_metadata(not, "source").unwrap(),
+        Some("alu.v:12")
+    );
+    assert_eq!(
+        builder.get_component_metadata(not, "category").unwrap(),
+        Some("control")
+    );
+    assert_eq!(
+        builder.get_component_metadata(not, "missing").unwrap(),
+        None
+    );
+    assert_eq!(
+        builder.get_component_metadata(other, "source").unwrap(),
+        None
+    );
+
+    // Tying wires rebuilds the graph, which must keep the metadata
+    builder.tie_wires(inverted, buffer).unwrap();
+    let sim = builder.build();
+
+    assert_eq!(
+        sim.get_component_metadata(not, "source").unwrap(),
+        Some("alu.v:12")
+    );
+    let mut metadata: Vec<_> = sim.iter_component_metadata(not).unwrap().collect();
+    metadata.sort_unstable();
+    assert_eq!(metadata, [("category", "control"), ("source", "alu.v:12")]);
+    assert_eq!(sim.iter_component_metadata(other).unwrap().count(), 0);
+}
+
+#[test]
+fn run_sim_min_steps() {
+    // A NOR gate feeding back into itself oscillates as soon as `reset` is released,
+    // and a counter counts its rising edges
+    let mut builder = SimulatorBuilder::default();
+    let clock = builder.add_wire(WIDTH_1).unwrap();
+    let reset = builder.add_wire(WIDTH_1).unwrap();
+    let one = builder.add_wire(WIDTH_8).unwrap();
+    let count = builder.add_wire(WIDTH_8).unwrap();
+    let next = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_nor_gate(&[clock, reset], clock).unwrap();
+    builder.add_add(count, one, next).unwrap();
+    builder
+        .add_delay(next, count, clock, ClockPolarity::Rising)
+        .unwrap();
+    builder
+        .set_wire_drive(reset, &LogicState::from_bool(true))
+        .unwrap();
+    builder
+        .set_wire_drive(one, &LogicState::from_u32(1, WIDTH_8))
+        .unwrap();
+
+    let mut sim = builder.build();
+    sim.write_register_by_output(count, &LogicState::from_u32(0, WIDTH_8))
+        .unwrap();
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+    let [state, _] = sim.get_wire_state_and_drive(clock).unwrap();
+    assert_eq!(state, LogicState::from_bool(false));
+
+    sim.set_wire_drive(reset, &LogicState::from_bool(false))
+        .unwrap();
+    sim.enable_step_histogram();
+
+    // The clock toggles every step, so every run of 8 steps counts 4 rising edges,
+    // except for the first one, which starts on the initial update of all wires
+    let mut counts = Vec::new();
+    for _ in 0..4 {
+        assert!(matches!(
+            sim.run_sim_min_steps(8, 100),
+            SimulationRunResult::Ok
+        ));
+        assert!(!sim.is_settled());
+        let [state, _] = sim.get_wire_state_and_drive(count).unwrap();
+        counts.push(state.to_owned());
+    }
+    let expected: Vec<_> = [3, 7, 11, 15]
+        .into_iter()
+        .map(|count| LogicState::from_u32(count, WIDTH_8))
+        .collect();
+    assert_eq!(counts, expected);
+    assert_eq!(sim.step_histogram().unwrap(), &BTreeMap::from([(8, 4)]));
+
+    // Oscillation is still detected if `max_steps` ends the run first
+    assert!(matches!(
+        sim.run_sim(8),
+        SimulationRunResult::MaxStepsReached
+    ));
+    assert!(matches!(
+        sim.run_sim_min_steps(8, 4),
+        SimulationRunResult::MaxStepsReached
+    ));
+    assert!(!sim.is_settled());
+
+    // A circuit that settles early ends the run right away
+    let mut builder = SimulatorBuilder::default();
+    let input = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_not_gate(input, output).unwrap();
+    builder
+        .set_wire_drive(input, &LogicState::from_u32(0x0F, WIDTH_8))
+        .unwrap();
+
+    let mut sim = builder.build();
+    sim.enable_step_histogram();
+    assert!(matches!(
+        sim.run_sim_min_steps(50, 100),
+        SimulationRunResult::Ok
+    ));
+    let histogram = sim.step_histogram().unwrap();
+    assert_eq!(histogram.len(), 1);
+    assert!(histogram.keys().all(|&steps| steps < 50));
+    assert!(sim.is_settled());
+    let [state, _] = sim.get_wire_state_and_drive(output).unwrap();
+    assert_eq!(state, LogicState::from_u32(0xF0, WIDTH_8));
+}
+
+#[test]
+fn get_wire_bit() {
+    let mut builder = SimulatorBuilder::default();
+
+    let input = builder.add_wire(WIDTH_8).unwrap();
+    let output = builder.add_wire(WIDTH_8).unwrap();
+    let probe = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_not_gate(input, output).unwrap();
+    builder
+        .set_wire_drive(input, &LogicState::from_u32(0xA5, WIDTH_8))
+        .unwrap();
+    builder
+        .set_wire_drive(
+            probe,
+            &LogicState::from_bit_planes(WIDTH_8, &[0b0010_0101], &[0b0011_0000]),
+        )
+        .unwrap();
+
+    let mut sim = builder.build();
+    assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+
+    for index in 0..8 {
+        let expected = LogicBitState::from_bool(((0x5A >> index) & 0x1) != 0);
+        assert_eq!(sim.get_wire_bit(output, index).unwrap(), expected);
+    }
+
+    let expected = [
+        LogicBitState::Logic1,
+        LogicBitState::Logic0,
+        LogicBitState::Logic1,
+        LogicBitState::Logic0,
+        LogicBitState::HighZ,
+        LogicBitState::Undefined,
+        LogicBitState::Logic0,
+        LogicBitState::Logic0,
+    ];
+    for (index, expected) in expected.into_iter().enumerate() {
+        assert_eq!(sim.get_wire_bit(probe, index as u32).unwrap(), expected);
+    }
+
+    assert!(matches!(
+        sim.get_wire_bit(output, 8),
+        Err(GetWireBitError::IndexOutOfRange)
+    ));
+}
+
+#[test]
+fn ordered_start() {
+    let mut builder = SimulatorBuilder::default();
+
+    let one = builder.add_wire(WIDTH_8).unwrap();
+    let first = builder.add_wire(WIDTH_8).unwrap();
+    builder
+        .set_wire_drive(one, &LogicState::from_u32(1, WIDTH_8))
+        .unwrap();
+    builder
+        .set_wire_drive(first, &LogicState::from_u32(0, WIDTH_8))
+        .unwrap();
+
+    let mut sum = first;
+    for _ in 0..100 {
+        let next = builder.add_wire(WIDTH_8).unwrap();
+        builder.add_add(sum, one, next).unwrap();
+        sum = next;
+    }
+
+    let mut sim = builder.build_with_ordered_start();
+    sim.enable_step_histogram();
+    assert!(matches!(sim.run_sim(2), SimulationRunResult::Ok));
+    let [state, _] = sim.get_wire_state_and_drive(sum).unwrap();
+    assert_eq!(state, LogicState::from_u32(100, WIDTH_8));
+
+    sim.set_wire_drive(first, &LogicState::from_u32(50, WIDTH_8))
+        .unwrap();
+    assert!(matches!(sim.run_sim(2), SimulationRunResult::Ok));
+    let [state, _] = sim.get_wire_state_and_drive(sum).unwrap();
+    assert_eq!(state, LogicState::from_u32(150, WIDTH_8));
+
+    assert_eq!(
+        sim.step_histogram().unwrap().iter().collect::<Vec<_>>(),
+        [(&1, &2)]
+    );
+}
+
+#[test]
+fn ordered_start_with_cycles() {
+    let mut builder = SimulatorBuilder::default();
+
+    let a = builder.add_wire(WIDTH_1).unwrap();
+    let b = builder.add_wire(WIDTH_1).unwrap();
+    let output = builder.add_wire(WIDTH_1).unwrap();
+    builder.add_and_gate(&[a, output], b).unwrap();
+    builder.add_not_gate(b, output).unwrap();
+    builder
+        .set_wire_drive(a, &LogicState::from_bool(false))
+        .unwrap();
+
+    let mut sim = builder.build_with_ordered_start();
+    assert!(matches!(sim.run_sim(10), SimulationRunResult::Ok));
+    let [state, _] = sim.get_wire_state_and_drive(output).unwrap();
+    assert_eq!(state, LogicState::from_bool(true));
+}
+
+#[test]
+fn ordered_start_register_chain() {
+    const STAGES: usize = 3;
+
+    // The downstream registers are added first, so they come first in the component order
+    fn build(ordered_start: bool) -> (Simulator, WireId, WireId, Vec<WireId>) {
+        let mut builder = SimulatorBuilder::default();
+
+        let clock = builder.add_wire(WIDTH_1).unwrap();
+        let input = builder.add_wire(WIDTH_8).unwrap();
+        let stages: Vec<_> = (0..STAGES)
+            .map(|_| builder.add_wire(WIDTH_8).unwrap())
+            .collect();
+        for i in (0..STAGES).rev() {
+            let data_in = if i == 0 { input } else { stages[i - 1] };
+            builder
+                .add_delay(data_in, stages[i], clock, ClockPolarity::Rising)
+                .unwrap();
+        }
+
+        let sim = if ordered_start {
+            builder.build_with_ordered_start()
+        } else {
+            builder.build()
+        };
+        (sim, clock, input, stages)
+    }
+
+    // Records all stages after every step of the clock
+    fn run(ordered_start: bool) -> Vec<Vec<LogicState>> {
+        let (mut sim, clock, input, stages) = build(ordered_start);
+
+        let mut states = Vec::new();
+        for value in 1..=5 {
+            sim.set_wire_drive(input, &LogicState::from_u32(value, WIDTH_8))
+                .unwrap();
+            for clock_state in [false, true] {
+                sim.set_wire_drive(clock, &LogicState::from_bool(clock_state))
+                    .unwrap();
+                assert!(matches!(sim.run_sim(8), SimulationRunResult::Ok));
+
+                states.push(
+                    stages
+                        .iter()
+                        .map(|&stage| {
+                            let [state, _] = sim.get_wire_state_and_drive(stage).unwrap();
+                            state.to_owned()
+                        })
+                        .collect(),
+                );
+            }
+        }
+        states
+    }
+
+    let expected = run(false);
+    let last = expected.last().unwrap();
+    assert_eq!(last[STAGES - 1], LogicState::from_u32(3, WIDTH_8));
+    assert_eq!(run(true), expected);
+}
+
+#[test]
+fn state_image() {
+    let mut builder = SimulatorBuilder::default();
+
+    let data_in = builder.add_wire(WIDTH_16).unwrap();
+    let data_out = builder.add_wire(WIDTH_16).unwrap();
+    let push = builder.add_wire(WIDTH_1).unwrap();
+    let pop = builder.add_wire(WIDTH_1).unwrap();
+    let clock = builder.add_wire(WIDTH_1).unwrap();
+    let full = builder.add_wire(WIDTH_1).unwrap();
+    let empty = builder.add_wire(WIDTH_1).unwrap();
+    let register_in = builder.add_wire(WIDTH_8).unwrap();
+    let register_out = builder.add_wire(WIDTH_8).unwrap();
+    builder
+        .add_fifo(
+            data_in,
+            data_out,
+            push,
+            pop,
+            clock,
+            full,
+            empty,
+            NonZeroUsize::new(2).unwrap(),
+            ClockPolarity::Rising,
+        )
+        .unwrap();
+    builder
+        .add_delay(register_in, register_out, clock, ClockPolarity::Rising)
+        .unwrap();
+
+    let mut sim = builder.build();
+    sim.set_wire_drive(clock, &LogicState::from_bool(false))
+        .unwrap();
+    sim.set_wire_drive(pop, &LogicState::from_bool(false))
+        .unwrap();
+    sim.set_wire_drive(push, &LogicState::from_bool(true))
+        .unwrap();
+    sim.run_sim(4).unwrap();
+
+    for value in [0x1234, 0x5678] {
+        sim.set_wire_drive(data_in, &LogicState::from_u32(value, WIDTH_16))
+            .unwrap();
+        sim.set_wire_drive(clock, &LogicState::from_bool(true))
+            .unwrap();
+        sim.run_sim(4).unwrap();
+        sim.set_wire_drive(clock, &LogicState::from_bool(false))
+            .unwrap();
+        sim.run_sim(4).unwrap();
+    }
+
+    sim.set_wire_drive(push, &LogicState::from_bool(false))
+        .unwrap();
+    sim.write_register_by_output(register_out, &LogicState::from_u32(0xA5, WIDTH_8))
+        .unwrap();
+    sim.run_sim(4).unwrap();
+
+    let read = |sim: &Simulator| {
+        let [data_state, _] = sim.get_wire_state_and_drive(data_out).unwrap();
+        let [full_state, _] = sim.get_wire_state_and_drive(full).unwrap();
+        let [register_state, _] = sim.get_wire_state_and_drive(register_out).unwrap();
+        (
+            data_state.to_owned(),
+            full_state.to_owned(),
+            register_state.to_owned(),
+        )
+    };
+
+    let expected = (
+        LogicState::from_u32(0x1234, WIDTH_16),
+        LogicState::from_bool(true),
+        LogicState::from_u32(0xA5, WIDTH_8),
+    );
+    assert_eq!(read(&sim), expected);
+
+    let mut image = Vec::new();
+    sim.save_state_image(&mut image).unwrap();
+
+    sim.reset();
+    sim.run_sim(4).unwrap();
+    let [empty_state, _] = sim.get_wire_state_and_drive(empty).unwrap();
+    assert_eq!(empty_state, LogicState::from_bool(true));
+
+    sim.load_state_image(image.as_slice()).unwrap();
+    sim.run_sim(4).unwrap();
+    assert_eq!(read(&sim), expected);
+
+    // Images only load into simulators with the same memory components
+    let mut builder = SimulatorBuilder::default();
+    let register_in = builder.add_wire(WIDTH_16).unwrap();
+    let register_out = builder.add_wire(WIDTH_16).unwrap();
+    let clock = builder.add_wire(WIDTH_1).unwrap();
+    builder
+        .add_delay(register_in, register_out, clock, ClockPolarity::Rising)
+        .unwrap();
+    let mut other = builder.build();
+
+    assert!(matches!(
+        other.load_state_image(image.as_slice()),
+        Err(StateImageError::LayoutMismatch)
+    ));
+    assert!(matches!(
+        sim.load_state_image(&image[..(image.len() - 1)]),
+        Err(StateImageError::InvalidFormat)
+    ));
+}
+
+#[test]
+fn state_image_entry_lengths() {
+    let mut builder = SimulatorBuilder::default();
+    let addr = builder.add_wire(bit_width!(2)).unwrap();
+    let data_in = builder.add_wire(WIDTH_8).unwrap();
+    let data_out = builder.add_wire(WIDTH_8).unwrap();
+    let write = builder.add_wire(WIDTH_1).unwrap();
+    let clock = builder.add_wire(WIDTH_1).unwrap();
+    let register_out = builder.add_wire(WIDTH_8).unwrap();
+    let ram = builder
+        .add_ram(
+            addr,
+            data_in,
+            addr,
+            data_out,
+            write,
+            clock,
+            ClockPolarity::Rising,
+        )
+        .unwrap();
+    let register = builder
+        .add_delay(data_in, register_out, clock, ClockPolarity::Rising)
+        .unwrap();
+    let mut sim = builder.build();
+
+    let mut image = Vec::new();
+    sim.save_state_image(&mut image).unwrap();
+
+    // Rewrites the image with the entries of one component resized
+    let resize = |id: ComponentId, len: usize| {
+        let read =
+            |offset: usize| u32::from_le_bytes(image[offset..(offset + 4)].try_into().unwrap());
+        let mut resized = image[..12].to_vec();
+        let mut offset = 12;
+        while offset < image.len() {
+            let (entry_id, entry_len) = (read(offset), read(offset + 8) as usize);
+            // Two bit planes of a single word per entry
+            let entries = &image[(offset + 12)..(offset + 12 + (entry_len * 8))];
+            resized.extend_from_slice(&image[offset..(offset + 8)]);
+            offset += 12 + entries.len();
+
+            if entry_id == id.to_bits() {
+                resized.extend_from_slice(&(len as u32).to_le_bytes());
+                resized.extend(entries.iter().copied().cycle().take(len * 8));
+            } else {
+                resized.extend_from_slice(&(entry_len as u32).to_le_bytes());
+                resized.extend_from_slice(entries);
+            }
+        }
+        resized
+    };
+
+    assert_eq!(resize(ram, 4), image);
+    for (id, len) in [(ram, 3), (ram, 5), (register, 0), (register, 2)] {
+        assert!(matches!(
+            sim.load_state_image(resize(id, len).as_slice()),
+            Err(StateImageError::LayoutMismatch)
+        ));
+    }
+    for len in 0..image.len() {
+        assert!(matches!(
+            sim.load_state_image(&image[..len]),
+            Err(StateImageError::InvalidFormat)
+        ));
+    }
+
+    // Nothing was applied by the rejected images
+    let mut unchanged = Vec::new();
+    sim.save_state_image(&mut unchanged).unwrap();
+    assert_eq!(unchanged, image);
+}
+
+#[test]
+fn find_multiply_driven_wires() {
+    let mut builder = SimulatorBuilder::default();
+
+    let a = builder.add_wire(WIDTH_8).unwrap();
+    let b = builder.add_wire(WIDTH_8).unwrap();
+    let single = builder.add_wire(WIDTH_8).unwrap();
+    let double = builder.add_wire(WIDTH_8).unwrap();
+    let tied = builder.add_wire(WIDTH_8).unwrap();
+    builder.add_not_gate(a, single).unwrap();
+    let first = builder.add_not_gate(a, double).unwrap();
+    let second = builder.add_not_gate(b, double).unwrap();
+    assert_eq!(
+        builder.find_multiply_driven_wires(),
+        [(double, SmallVec::from_slice(&[first, second]))]
+    );
+
+    // A driver on a tied wire contends with the drivers of the wire it is tied to
+    let third = builder.add_not_gate(b, tied).unwrap();
+    builder.tie_wires(tied, single).unwrap();
+    let found = builder.find_multiply_driven_wires();
+    assert_eq!(found.len(), 2);
+    assert!(found.contains(&(double, SmallVec::from_slice(&[first, second]))));
+    assert!(found
+        .iter()
+        .any(|(_, components)| components.contains(&third) && (components.len() == 2)));
+}
+
+#[test]
+fn test_fixture() {
+    use import::{TestFixture, TestFixtureError};
+
+    let mut builder = SimulatorBuilder::default();
+
+    let a = builder.add_wire(WIDTH_8).unwrap();
+    let b = builder.add_wire(WIDTH_8).unwrap();
+    let sum = builder.add_wire(WIDTH_8).unwrap();
+    let add = builder.add_add(a, b, sum).unwrap();
+    builder.mark_input(a, "a").unwrap();
+    builder.mark_input(b, "b").unwrap();
+    builder.mark_output(sum, "sum").unwrap();
+
+    let sim = builder.build();
+    let ports = sim.ports().clone();
+    let mut fixture = TestFixture::new(sim, &ports);
+
+    for (va, vb) in [(1, 2), (100, 27), (200, 100)] {
+        fixture.set("a", va).unwrap();
+        fixture.set("b", vb).unwrap();
+        fixture.run().unwrap();
+
+        let expected = ((va + vb) & 0xFF) as u32;
+        assert_eq!(
+            fixture.get("sum").unwrap(),
+            LogicState::from_u32(expected, WIDTH_8)
+        );
+    }
+
+    fixture
+        .set_state("a", &LogicState::from_u32(5, WIDTH_8))
+        .unwrap();
+    assert_eq!(fixture.get("a").unwrap(), LogicState::from_u32(5, WIDTH_8));
+
+    assert!(matches!(
+        fixture.set("c", 0),
+        Err(TestFixtureError::UnknownPort(name)) if &*name == "c"
+    ));
+    assert!(matches!(
+        fixture.set("sum", 0),
+        Err(TestFixtureError::UnknownPort(_))
+    ));
+    assert!(matches!(
+        fixture.get("c"),
+        Err(TestFixtureError::UnknownPort(_))
+    ));
+    assert!(matches!(
+        fixture.set("a", 0x100),
+        Err(TestFixtureError::ValueTooWide)
+    ));
+    assert!(matches!(
+        fixture.set_state("a", &LogicState::from_bool(true)),
+        Err(TestFixtureError::WidthMismatch { expected, actual })
+            if (expected == WIDTH_8) && (actual == WIDTH_1)
+    ));
+
+    // Inputs read back their drive, outputs their state
+    fixture.set("b", 7).unwrap();
+    assert_eq!(fixture.get("b").unwrap(), LogicState::from_u32(7, WIDTH_8));
+    assert_eq!(
+        fixture.get("sum").unwrap(),
+        LogicState::from_u32(44, WIDTH_8)
+    );
+
+    // A port whose wire is not part of the simulation
+    let mut builder = SimulatorBuilder::default();
+    let mut foreign = builder.add_wire(WIDTH_8).unwrap();
+    for _ in 0..8 {
+        foreign = builder.add_wire(WIDTH_8).unwrap();
+    }
+    let mut ports = ports;
+    ports.inputs.insert("foreign".into(), foreign);
+    let mut fixture = TestFixture::new(fixture.into_sim(), &ports);
+    assert!(matches!(
+        fixture.set("foreign", 0),
+        Err(TestFixtureError::InvalidWireId)
+    ));
+    assert!(matches!(
+        fixture.set_state("foreign", &LogicState::from_u32(0, WIDTH_8)),
+        Err(TestFixtureError::InvalidWireId)
+    ));
+    assert!(matches!(
+        fixture.get("foreign"),
+        Err(TestFixtureError::InvalidWireId)
+    ));
+
+    // An input port that is also driven inside the module
+    ports.inputs.insert("feedback".into(), sum);
+    let mut fixture = TestFixture::new(fixture.into_sim(), &ports);
+    assert!(matches!(
+        fixture.set("feedback", 0),
+        Err(TestFixtureError::DrivenInternally { port, component })
+            if (&*port == "feedback") && (component == add)
+    ));
+}
@@ -26,6 +26,7 @@ impl From<CopyFromResult> for WireUpdateResult {
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct Wire {
     bit_width: BitWidth,
     state_id: WireStateId,
@@ -64,8 +65,31 @@ impl Wire {
         self.driving.as_slice()
     }
 
+    /// Makes the wire share the state of a tied wire
+    #[inline]
+    pub(crate) fn share_state(&mut self, state_id: WireStateId) {
+        self.state_id = state_id;
+    }
+
+    /// Removes all connections from the wire, so they can be moved to the wire it is tied to
+    #[inline]
+    pub(crate) fn take_connections(&mut self) -> (IdVec<OutputStateId>, IdVec<ComponentId>) {
+        (
+            std::mem::replace(&mut self.drivers, IdVec::new()),
+            std::mem::replace(&mut self.driving, IdVec::new()),
+        )
+    }
+
     pub(crate) fn add_driver(&mut self, output: OutputStateId) {
+        // Drivers are kept sorted so they are always combined in the same order,
+        // no matter in which order the components were connected.
         self.drivers.push(output);
+        let drivers = self.drivers.as_mut_slice();
+        let mut i = drivers.len() - 1;
+        while (i > 0) && (drivers[i - 1] > drivers[i]) {
+            drivers.swap(i - 1, i);
+            i -= 1;
+        }
     }
 
     pub(crate) fn add_driving(&mut self, component: ComponentId) {
@@ -158,6 +182,20 @@ impl Wire {
             copy_result.into()
         }
     }
+
+    /// Forces the state of the wire to Undefined after a conflict was detected
+    pub(crate) fn resolve_conflict(&self, mut wire_states: WireStateViewMut) -> WireUpdateResult {
+        let [mut state, _] = wire_states
+            .get_mut(self.state_id, self.bit_width)
+            .expect("invalid wire state ID");
+
+        let mut undefined = InlineLogicState::undefined(self.bit_width);
+        let (undefined_plane_0, undefined_plane_1) = undefined.bit_planes_mut();
+        *undefined_plane_0.last_mut().unwrap() &= self.bit_width.last_word_mask();
+        *undefined_plane_1.last_mut().unwrap() &= self.bit_width.last_word_mask();
+
+        state.copy_from(&undefined).into()
+    }
 }
 
 def_id_list!(WireList<WireId, Wire>);